@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use reqwest::RequestBuilder;
+use reqwest::header::HeaderValue;
+
+#[derive(Clone)]
+pub enum AuthConfig {
+    /// Sends `Authorization: Bearer <token>`.
+    Bearer(String),
+    /// Sends the key in a custom header, e.g. `X-API-Key`.
+    ApiKey { header: String, value: String },
+}
+
+impl AuthConfig {
+    pub(crate) fn apply(&self, request_builder: RequestBuilder) -> RequestBuilder {
+        match self {
+            AuthConfig::Bearer(token) => request_builder.bearer_auth(token),
+            AuthConfig::ApiKey { header, value } => match HeaderValue::from_str(value) {
+                Ok(mut header_value) => {
+                    header_value.set_sensitive(true);
+                    request_builder.header(header.as_str(), header_value)
+                }
+                // Let reqwest surface the invalid value when the request is sent.
+                Err(_) => request_builder.header(header.as_str(), value.as_str()),
+            },
+        }
+    }
+}
+
+impl fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthConfig::Bearer(_) => f.debug_tuple("Bearer").field(&"<redacted>").finish(),
+            AuthConfig::ApiKey { header, .. } => f
+                .debug_struct("ApiKey")
+                .field("header", header)
+                .field("value", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// Header names whose values are credentials and are kept out of `Debug`
+/// output.
+const SENSITIVE_HEADERS: [&str; 5] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+    "api-key",
+];
+
+pub(crate) fn is_sensitive_header(name: &str) -> bool {
+    SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+}
+
+/// Formats default headers with the values of credential headers redacted.
+pub(crate) struct RedactedHeaders<'a>(pub(crate) &'a HashMap<String, String>);
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                let value = if is_sensitive_header(name) {
+                    "<redacted>"
+                } else {
+                    value.as_str()
+                };
+                (name, value)
+            }))
+            .finish()
+    }
+}
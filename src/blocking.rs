@@ -0,0 +1,742 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::Method;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::error::IngrainError;
+use crate::models::{
+    Dims, EmbeddingResponse, GenericMessageResponse, ImageClassificationResponse,
+    ImageEmbeddingResponse, LoadedModel, LoadedModelResponse, MetricsResponse,
+    ModelClassificationLabelsResponse, ModelEmbeddingDimsResponse, ModelLibrary, ModelMetadata,
+    ModelReload, ModelStats, RepositoryModelPage, RepositoryModelResponse, RepositoryPageRequest,
+    ServerInfoResponse, TextEmbeddingResponse,
+};
+use crate::{
+    ImageInput, ImageMime, IngrainClient, ModelResults, ResponseMeta, RetryOverride, RetryReport,
+    ServerTarget, Timed,
+};
+
+/// A synchronous wrapper around [`IngrainClient`] that drives each call on a
+/// small internal runtime. Methods mirror the async client without `async`.
+///
+/// Calls block the current thread, so they must not be made from within an
+/// async runtime; use [`IngrainClient`] there instead.
+#[derive(Clone, Debug)]
+pub struct IngrainBlockingClient {
+    inner: IngrainClient,
+    runtime: Arc<Runtime>,
+}
+
+impl IngrainBlockingClient {
+    pub fn new(model_server_url: &str, inference_server_url: &str) -> Self {
+        IngrainBlockingClient::from_client(IngrainClient::new(
+            model_server_url,
+            inference_server_url,
+        ))
+    }
+
+    /// Wraps an already configured client, e.g. one made with
+    /// [`IngrainClient::builder`].
+    pub fn from_client(client: IngrainClient) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the blocking client runtime");
+        IngrainBlockingClient {
+            inner: client,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client calls are forwarded to.
+    pub fn inner(&self) -> &IngrainClient {
+        &self.inner
+    }
+
+    /// See [`IngrainClient::with_request_id`].
+    pub fn with_request_id(&self, request_id: impl Into<String>) -> IngrainBlockingClient {
+        IngrainBlockingClient {
+            inner: self.inner.with_request_id(request_id),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`IngrainClient::with_retry_override`].
+    pub fn with_retry_override(&self, retry_override: RetryOverride) -> IngrainBlockingClient {
+        IngrainBlockingClient {
+            inner: self.inner.with_retry_override(retry_override),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`IngrainClient::with_deadline`].
+    pub fn with_deadline(&self, deadline: Instant) -> IngrainBlockingClient {
+        IngrainBlockingClient {
+            inner: self.inner.with_deadline(deadline),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`IngrainClient::with_auto_load`].
+    pub fn with_auto_load(&self, library: ModelLibrary) -> IngrainBlockingClient {
+        IngrainBlockingClient {
+            inner: self.inner.with_auto_load(library),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`IngrainClient::clear_cache`].
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache();
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn request_json<B, Q, R>(
+        &self,
+        method: Method,
+        url_suffix: &str,
+        server: ServerTarget,
+        body: Option<&B>,
+        query: Option<&Q>,
+    ) -> Result<R, IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        self.block_on(
+            self.inner
+                .request_json(method, url_suffix, server, body, query),
+        )
+    }
+
+    pub fn request_json_with_meta<B, Q, R>(
+        &self,
+        method: Method,
+        url_suffix: &str,
+        server: ServerTarget,
+        body: Option<&B>,
+        query: Option<&Q>,
+    ) -> Result<(R, ResponseMeta), IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        self.block_on(
+            self.inner
+                .request_json_with_meta(method, url_suffix, server, body, query),
+        )
+    }
+
+    pub fn model_server_health(&self) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.model_server_health())
+    }
+
+    pub fn inference_server_health(&self) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.inference_server_health())
+    }
+
+    pub fn model_server_ready(&self) -> Result<bool, IngrainError> {
+        self.block_on(self.inner.model_server_ready())
+    }
+
+    pub fn model_server_live(&self) -> Result<bool, IngrainError> {
+        self.block_on(self.inner.model_server_live())
+    }
+
+    pub fn inference_server_ready(&self) -> Result<bool, IngrainError> {
+        self.block_on(self.inner.inference_server_ready())
+    }
+
+    pub fn inference_server_live(&self) -> Result<bool, IngrainError> {
+        self.block_on(self.inner.inference_server_live())
+    }
+
+    pub fn health_all(
+        &self,
+    ) -> (
+        Result<GenericMessageResponse, IngrainError>,
+        Result<GenericMessageResponse, IngrainError>,
+    ) {
+        self.block_on(self.inner.health_all())
+    }
+
+    pub fn model_server_info(&self) -> Result<ServerInfoResponse, IngrainError> {
+        self.block_on(self.inner.model_server_info())
+    }
+
+    pub fn loaded_models(&self) -> Result<LoadedModelResponse, IngrainError> {
+        self.block_on(self.inner.loaded_models())
+    }
+
+    pub fn loaded_model(&self, name: &str) -> Result<Option<LoadedModel>, IngrainError> {
+        self.block_on(self.inner.loaded_model(name))
+    }
+
+    pub fn loaded_models_by_library(
+        &self,
+        library: ModelLibrary,
+    ) -> Result<Vec<LoadedModel>, IngrainError> {
+        self.block_on(self.inner.loaded_models_by_library(library))
+    }
+
+    pub fn is_model_loaded(&self, name: &str) -> Result<bool, IngrainError> {
+        self.block_on(self.inner.is_model_loaded(name))
+    }
+
+    pub fn repository_models(&self) -> Result<RepositoryModelResponse, IngrainError> {
+        self.block_on(self.inner.repository_models())
+    }
+
+    pub fn repository_models_if_changed(
+        &self,
+    ) -> Result<Option<RepositoryModelResponse>, IngrainError> {
+        self.block_on(self.inner.repository_models_if_changed())
+    }
+
+    pub fn repository_models_paged(
+        &self,
+        page: RepositoryPageRequest,
+    ) -> Result<RepositoryModelPage, IngrainError> {
+        self.block_on(self.inner.repository_models_paged(page))
+    }
+
+    pub fn wait_for_model(
+        &self,
+        name: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<(), IngrainError> {
+        self.block_on(self.inner.wait_for_model(name, timeout, poll_interval))
+    }
+
+    pub fn metrics(&self) -> Result<MetricsResponse, IngrainError> {
+        self.block_on(self.inner.metrics())
+    }
+
+    pub fn metrics_for(&self, name: &str) -> Result<Option<ModelStats>, IngrainError> {
+        self.block_on(self.inner.metrics_for(name))
+    }
+
+    pub fn loaded_models_with_metrics(
+        &self,
+    ) -> Result<Vec<(LoadedModel, Option<ModelStats>)>, IngrainError> {
+        self.block_on(self.inner.loaded_models_with_metrics())
+    }
+
+    pub fn load_model(
+        &self,
+        name: impl Into<String>,
+        library: ModelLibrary,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.load_model(name, library))
+    }
+
+    pub fn load_model_inferred(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.load_model_inferred(name))
+    }
+
+    pub fn warmup_model(
+        &self,
+        name: impl Into<String>,
+        library: ModelLibrary,
+    ) -> Result<Duration, IngrainError> {
+        self.block_on(self.inner.warmup_model(name, library))
+    }
+
+    pub fn load_models(
+        &self,
+        models: Vec<(String, ModelLibrary)>,
+        concurrency: usize,
+    ) -> ModelResults {
+        self.block_on(self.inner.load_models(models, concurrency))
+    }
+
+    pub fn unload_model(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.unload_model(name))
+    }
+
+    pub fn unload_all_models(&self) -> Result<ModelResults, IngrainError> {
+        self.block_on(self.inner.unload_all_models())
+    }
+
+    pub fn delete_model(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.delete_model(name))
+    }
+
+    pub fn delete_model_if_unloaded(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        self.block_on(self.inner.delete_model_if_unloaded(name))
+    }
+
+    pub fn reload_model(
+        &self,
+        name: impl Into<String>,
+        library: ModelLibrary,
+        ready_timeout: Option<Duration>,
+    ) -> Result<ModelReload, IngrainError> {
+        self.block_on(self.inner.reload_model(name, library, ready_timeout))
+    }
+
+    pub fn embed_text(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_text(name, text, normalize, n_dims))
+    }
+
+    pub fn embed_text_timed(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<Timed<TextEmbeddingResponse>, IngrainError> {
+        self.block_on(self.inner.embed_text_timed(name, text, normalize, n_dims))
+    }
+
+    pub fn embed_text_with_report(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> (Result<TextEmbeddingResponse, IngrainError>, RetryReport) {
+        self.block_on(
+            self.inner
+                .embed_text_with_report(name, text, normalize, n_dims),
+        )
+    }
+
+    pub fn embed_text_with_meta(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<(TextEmbeddingResponse, ResponseMeta), IngrainError> {
+        self.block_on(
+            self.inner
+                .embed_text_with_meta(name, text, normalize, n_dims),
+        )
+    }
+
+    pub fn embed_text_one(
+        &self,
+        name: impl Into<String>,
+        text: impl Into<String>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<Vec<f32>, IngrainError> {
+        self.block_on(self.inner.embed_text_one(name, text, normalize, n_dims))
+    }
+
+    pub fn embed_text_raw(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<serde_json::Value, IngrainError> {
+        self.block_on(self.inner.embed_text_raw(name, text, normalize, n_dims))
+    }
+
+    pub fn embed_text_checked(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_text_checked(name, text, normalize, n_dims))
+    }
+
+    pub fn embed_text_batched(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        batch_size: usize,
+        concurrency: usize,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_text_batched(
+            name,
+            text,
+            batch_size,
+            concurrency,
+            normalize,
+            n_dims,
+        ))
+    }
+
+    pub fn embed_text_batched_partial(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        batch_size: usize,
+        concurrency: usize,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Vec<Result<Vec<f32>, IngrainError>> {
+        self.block_on(self.inner.embed_text_batched_partial(
+            name,
+            text,
+            batch_size,
+            concurrency,
+            normalize,
+            n_dims,
+        ))
+    }
+
+    pub fn embed_image(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_image(
+            name,
+            image,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    pub fn embed_image_timed(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Timed<ImageEmbeddingResponse>, IngrainError> {
+        self.block_on(self.inner.embed_image_timed(
+            name,
+            image,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    pub fn embed_image_with_report(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> (Result<ImageEmbeddingResponse, IngrainError>, RetryReport) {
+        self.block_on(self.inner.embed_image_with_report(
+            name,
+            image,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    pub fn embed_image_inputs(
+        &self,
+        name: impl Into<String>,
+        images: Vec<ImageInput>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_image_inputs(
+            name,
+            images,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    pub fn embed_image_one(
+        &self,
+        name: impl Into<String>,
+        image: impl Into<String>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Vec<f32>, IngrainError> {
+        self.block_on(self.inner.embed_image_one(
+            name,
+            image,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    pub fn embed_image_files(
+        &self,
+        name: impl Into<String>,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_image_files(name, paths, normalize, n_dims))
+    }
+
+    pub fn embed_image_bytes(
+        &self,
+        name: impl Into<String>,
+        images: Vec<(Vec<u8>, ImageMime)>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        self.block_on(
+            self.inner
+                .embed_image_bytes(name, images, normalize, n_dims),
+        )
+    }
+
+    pub fn embed(
+        &self,
+        name: impl Into<String>,
+        text: Option<Vec<String>>,
+        image: Option<Vec<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<EmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed(
+            name,
+            text,
+            image,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn embed_batched(
+        &self,
+        name: impl Into<String>,
+        text: Option<Vec<String>>,
+        image: Option<Vec<String>>,
+        batch_size: usize,
+        concurrency: usize,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<EmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_batched(
+            name,
+            text,
+            image,
+            batch_size,
+            concurrency,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    /// See [`IngrainClient::embed_split`].
+    pub fn embed_split(
+        &self,
+        name: impl Into<String>,
+        text: Option<Vec<String>>,
+        image: Option<Vec<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<EmbeddingResponse, IngrainError> {
+        self.block_on(self.inner.embed_split(
+            name,
+            text,
+            image,
+            normalize,
+            n_dims,
+            image_download_headers,
+        ))
+    }
+
+    pub fn classify_image(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageClassificationResponse, IngrainError> {
+        self.block_on(
+            self.inner
+                .classify_image(name, image, image_download_headers),
+        )
+    }
+
+    pub fn classify_image_timed(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Timed<ImageClassificationResponse>, IngrainError> {
+        self.block_on(
+            self.inner
+                .classify_image_timed(name, image, image_download_headers),
+        )
+    }
+
+    pub fn classify_image_batched(
+        &self,
+        name: impl Into<String>,
+        images: Vec<String>,
+        batch_size: usize,
+        concurrency: usize,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageClassificationResponse, IngrainError> {
+        self.block_on(self.inner.classify_image_batched(
+            name,
+            images,
+            batch_size,
+            concurrency,
+            image_download_headers,
+        ))
+    }
+
+    pub fn classify_image_labeled(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Vec<Vec<(String, f32)>>, IngrainError> {
+        self.block_on(
+            self.inner
+                .classify_image_labeled(name, image, image_download_headers),
+        )
+    }
+
+    pub fn classify_image_top_k(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        k: usize,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Vec<Vec<(String, f32)>>, IngrainError> {
+        self.block_on(
+            self.inner
+                .classify_image_top_k(name, image, k, image_download_headers),
+        )
+    }
+
+    pub fn model_classification_labels(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<ModelClassificationLabelsResponse, IngrainError> {
+        self.block_on(self.inner.model_classification_labels(name))
+    }
+
+    pub fn model_embedding_size(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<ModelEmbeddingDimsResponse, IngrainError> {
+        self.block_on(self.inner.model_embedding_size(name))
+    }
+
+    pub fn model_metadata(&self, name: impl Into<String>) -> Result<ModelMetadata, IngrainError> {
+        self.block_on(self.inner.model_metadata(name))
+    }
+}
+
+impl From<IngrainClient> for IngrainBlockingClient {
+    fn from(client: IngrainClient) -> Self {
+        IngrainBlockingClient::from_client(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::Method::{GET, POST};
+    use httpmock::MockServer;
+
+    #[test]
+    fn test_blocking_health() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "Model server healthy"}"#);
+        });
+
+        let client = IngrainBlockingClient::new(&server.url(""), "http://localhost:8686");
+
+        let response = client.model_server_health().unwrap();
+
+        assert_eq!(response.message, "Model server healthy");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_blocking_embed_text() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""name":"test-model""#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2, 0.3]], "processingTimeMs": 5.0}"#);
+        });
+
+        let client = IngrainBlockingClient::new("http://localhost:8687", &server.url(""));
+
+        let response = client
+            .embed_text("test-model", ["hello"], None, None)
+            .unwrap();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2, 0.3]]);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_blocking_http_error() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(500).body("boom");
+        });
+
+        let client = IngrainBlockingClient::new(&server.url(""), "http://localhost:8686");
+
+        assert!(matches!(
+            client.loaded_models(),
+            Err(IngrainError::Http { .. })
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Embedding sizes and classification labels by model name. Both are fixed
+/// while a model stays loaded, so entries are only dropped when the model is
+/// unloaded or deleted through this client, or on
+/// [`IngrainClient::clear_cache`](crate::IngrainClient::clear_cache).
+#[derive(Debug, Default)]
+pub(crate) struct MetadataCache {
+    embedding_sizes: Mutex<HashMap<String, u64>>,
+    labels: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl MetadataCache {
+    pub(crate) fn embedding_size(&self, name: &str) -> Option<u64> {
+        self.embedding_sizes.lock().unwrap().get(name).copied()
+    }
+
+    pub(crate) fn set_embedding_size(&self, name: &str, size: u64) {
+        self.embedding_sizes
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), size);
+    }
+
+    pub(crate) fn labels(&self, name: &str) -> Option<Vec<String>> {
+        self.labels.lock().unwrap().get(name).cloned()
+    }
+
+    pub(crate) fn set_labels(&self, name: &str, labels: &[String]) {
+        self.labels
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), labels.to_vec());
+    }
+
+    pub(crate) fn invalidate(&self, name: &str) {
+        self.embedding_sizes.lock().unwrap().remove(name);
+        self.labels.lock().unwrap().remove(name);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.embedding_sizes.lock().unwrap().clear();
+        self.labels.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidate_only_drops_named_model() {
+        let cache = MetadataCache::default();
+        cache.set_embedding_size("a", 384);
+        cache.set_embedding_size("b", 512);
+        cache.set_labels("a", &["cat".to_string()]);
+
+        cache.invalidate("a");
+
+        assert_eq!(cache.embedding_size("a"), None);
+        assert_eq!(cache.labels("a"), None);
+        assert_eq!(cache.embedding_size("b"), Some(512));
+
+        cache.clear();
+        assert_eq!(cache.embedding_size("b"), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::IngrainError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        since: Instant,
+    },
+    /// The cooldown has passed and one trial call is in flight.
+    HalfOpen,
+}
+
+/// One circuit breaker per server URL. A call that fails with a network
+/// error, timeout or 5xx after its retries counts as one failure; any other
+/// response shows the server is up and closes the breaker again.
+#[derive(Debug)]
+pub(crate) struct CircuitBreakers {
+    failure_threshold: u32,
+    cooldown: Duration,
+    states: Mutex<HashMap<String, State>>,
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        CircuitBreakers {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fails fast while the breaker for `server` is open. Once the cooldown
+    /// has passed a single call is let through to test the server; others
+    /// keep failing fast until it completes.
+    pub(crate) fn check(&self, server: &str) -> Result<(), IngrainError> {
+        let mut states = self.states.lock().unwrap();
+        let Some(state) = states.get_mut(server) else {
+            return Ok(());
+        };
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { since } if since.elapsed() >= self.cooldown => {
+                *state = State::HalfOpen;
+                Ok(())
+            }
+            State::Open { .. } | State::HalfOpen => Err(IngrainError::CircuitOpen {
+                server: server.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn record_success(&self, server: &str) {
+        self.states.lock().unwrap().remove(server);
+    }
+
+    pub(crate) fn record_failure(&self, server: &str) {
+        let mut states = self.states.lock().unwrap();
+        let state = states
+            .entry(server.to_string())
+            .or_insert(State::Closed { failures: 0 });
+        *state = match *state {
+            State::Closed { failures } if failures + 1 < self.failure_threshold => State::Closed {
+                failures: failures + 1,
+            },
+            _ => State::Open {
+                since: Instant::now(),
+            },
+        };
+    }
+
+    /// Called when a call ends without telling anything about the server,
+    /// e.g. it was cancelled, so that a trial call does not leave the
+    /// breaker half-open forever.
+    pub(crate) fn record_abandoned(&self, server: &str) {
+        let mut states = self.states.lock().unwrap();
+        if let Some(state) = states.get_mut(server)
+            && *state == State::HalfOpen
+        {
+            let now = Instant::now();
+            *state = State::Open {
+                since: now.checked_sub(self.cooldown).unwrap_or(now),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_then_half_opens_after_cooldown() {
+        let breakers = CircuitBreakers::new(2, Duration::ZERO);
+
+        breakers.record_failure("a");
+        assert!(breakers.check("a").is_ok());
+        breakers.record_failure("a");
+
+        // The cooldown is zero, so the next check is the trial call and any
+        // concurrent call fails fast until it finishes.
+        assert!(breakers.check("a").is_ok());
+        assert!(matches!(
+            breakers.check("a"),
+            Err(IngrainError::CircuitOpen { .. })
+        ));
+        assert!(breakers.check("b").is_ok());
+
+        breakers.record_success("a");
+        assert!(breakers.check("a").is_ok());
+        assert!(breakers.check("a").is_ok());
+    }
+}
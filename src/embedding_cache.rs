@@ -0,0 +1,87 @@
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+
+use crate::models::Dims;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    name: String,
+    normalize: Option<bool>,
+    n_dims: Option<Dims>,
+    text: String,
+}
+
+/// Text embeddings by model, request options and text, evicting the least
+/// recently used entry once `capacity` entries are held.
+#[derive(Debug)]
+pub(crate) struct EmbeddingCache {
+    entries: Mutex<LruCache<Key, Vec<f32>>>,
+}
+
+impl EmbeddingCache {
+    /// A `capacity` of zero is treated as one.
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        EmbeddingCache {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    pub(crate) fn get(
+        &self,
+        name: &str,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        text: &str,
+    ) -> Option<Vec<f32>> {
+        let key = Key {
+            name: name.to_string(),
+            normalize,
+            n_dims,
+            text: text.to_string(),
+        };
+        self.entries.lock().unwrap().get(&key).cloned()
+    }
+
+    pub(crate) fn insert(
+        &self,
+        name: &str,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        text: &str,
+        embedding: Vec<f32>,
+    ) {
+        let key = Key {
+            name: name.to_string(),
+            normalize,
+            n_dims,
+            text: text.to_string(),
+        };
+        self.entries.lock().unwrap().put(key, embedding);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = EmbeddingCache::new(2);
+        cache.insert("m", None, None, "a", vec![1.0]);
+        cache.insert("m", None, None, "b", vec![2.0]);
+        assert!(cache.get("m", None, None, "a").is_some());
+
+        cache.insert("m", None, None, "c", vec![3.0]);
+
+        assert_eq!(cache.get("m", None, None, "a"), Some(vec![1.0]));
+        assert_eq!(cache.get("m", None, None, "b"), None);
+        assert_eq!(cache.get("m", Some(true), None, "c"), None);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::error::IngrainError;
+
+/// Returns `None` when the vectors have different lengths.
+pub fn dot(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() {
+        return None;
+    }
+    Some(a.iter().zip(b).map(|(x, y)| x * y).sum())
+}
+
+pub fn l2_norm(v: &[f32]) -> f32 {
+    v.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+/// Scales `v` to unit length in place. Zero-norm vectors are left as-is
+/// and `false` is returned.
+pub fn l2_normalize(v: &mut [f32]) -> bool {
+    let norm = l2_norm(v);
+    if norm == 0.0 {
+        return false;
+    }
+    for x in v.iter_mut() {
+        *x /= norm;
+    }
+    true
+}
+
+/// Returns `None` when the vectors have different lengths or either has a
+/// norm of zero.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    let dot = dot(a, b)?;
+    let norms = l2_norm(a) * l2_norm(b);
+    if norms == 0.0 {
+        return None;
+    }
+    Some(dot / norms)
+}
+
+/// Ranks `candidates` by cosine similarity to `query`, most similar first.
+/// Candidates for which the similarity is undefined (mismatched length or
+/// zero norm) are left out.
+pub fn rank_by_similarity(query: &[f32], candidates: &[Vec<f32>]) -> Vec<(usize, f32)> {
+    let mut ranked: Vec<(usize, f32)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| cosine_similarity(query, candidate).map(|s| (i, s)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+/// Returns the length shared by all rows, or zero when there are none.
+pub fn uniform_dims(rows: &[Vec<f32>]) -> Result<usize, IngrainError> {
+    let dims = rows.first().map_or(0, Vec::len);
+    match rows.iter().position(|values| values.len() != dims) {
+        Some(row) => Err(IngrainError::RaggedEmbeddings {
+            row,
+            expected: dims,
+            found: rows[row].len(),
+        }),
+        None => Ok(dims),
+    }
+}
+
+/// Stacks equally sized rows into an `(n, dims)` array. An empty input gives
+/// a `(0, 0)` array.
+#[cfg(feature = "ndarray")]
+pub fn to_array2(rows: &[Vec<f32>]) -> Result<ndarray::Array2<f32>, IngrainError> {
+    let dims = uniform_dims(rows)?;
+    let data = rows.iter().flatten().copied().collect();
+    Ok(
+        ndarray::Array2::from_shape_vec((rows.len(), dims), data)
+            .expect("row lengths were checked"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-6;
+
+    #[test]
+    fn test_cosine_similarity_identical() {
+        let similarity = cosine_similarity(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]).unwrap();
+        assert!((similarity - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_cosine_similarity_orthogonal() {
+        let similarity = cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).unwrap();
+        assert!(similarity.abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_cosine_similarity_opposite() {
+        let similarity = cosine_similarity(&[1.0, 2.0], &[-1.0, -2.0]).unwrap();
+        assert!((similarity + 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_mismatched_lengths_and_zero_norm() {
+        assert_eq!(dot(&[1.0, 2.0], &[1.0]), None);
+        assert_eq!(cosine_similarity(&[1.0, 2.0], &[1.0]), None);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), None);
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let mut v = vec![3.0, 4.0];
+        assert!(l2_normalize(&mut v));
+        assert!((v[0] - 0.6).abs() < EPSILON);
+        assert!((v[1] - 0.8).abs() < EPSILON);
+
+        let mut zero = vec![0.0, 0.0];
+        assert!(!l2_normalize(&mut zero));
+        assert_eq!(zero, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_rank_by_similarity() {
+        let query = [1.0, 0.0];
+        let candidates = vec![
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![-1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![1.0, 0.0, 0.0],
+        ];
+
+        let ranked = rank_by_similarity(&query, &candidates);
+
+        let order: Vec<usize> = ranked.iter().map(|(i, _)| *i).collect();
+        assert_eq!(order, vec![1, 3, 0, 2]);
+        assert!((ranked[0].1 - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_uniform_dims() {
+        assert_eq!(uniform_dims(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap(), 2);
+        assert_eq!(uniform_dims(&[]).unwrap(), 0);
+        assert!(matches!(
+            uniform_dims(&[vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0]]),
+            Err(IngrainError::RaggedEmbeddings {
+                row: 2,
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_to_array2() {
+        let rows = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let array = to_array2(&rows).unwrap();
+        assert_eq!(array.dim(), (2, 3));
+        assert_eq!(array[[1, 2]], 6.0);
+
+        assert_eq!(to_array2(&[]).unwrap().dim(), (0, 0));
+
+        let ragged = vec![vec![1.0, 2.0], vec![3.0]];
+        assert!(matches!(
+            to_array2(&ragged),
+            Err(IngrainError::RaggedEmbeddings {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+}
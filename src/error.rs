@@ -0,0 +1,273 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use reqwest::StatusCode;
+
+use crate::models::ModelState;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IngrainError {
+    /// The server responded with a non-success status code. `detail` holds
+    /// the server's error message when the body was a JSON
+    /// [`ApiErrorBody`](crate::models::ApiErrorBody).
+    Http {
+        status: StatusCode,
+        body: String,
+        detail: Option<String>,
+    },
+    /// The request could not be sent or the response could not be read.
+    Network(reqwest::Error),
+    /// No connection to the server could be established, e.g. it refused
+    /// the connection or its name did not resolve.
+    Connect(reqwest::Error),
+    /// The connect or request timeout elapsed before the server responded.
+    Timeout(reqwest::Error),
+    /// The response body could not be parsed into the expected type. `path`
+    /// is the JSON path of the offending value, e.g.
+    /// `modelStats[2].batchStats[0].batchSize`.
+    Deserialize {
+        source: serde_json::Error,
+        path: String,
+        body: String,
+    },
+    /// Every attempt failed; `last` holds the error from the final attempt.
+    RetriesExhausted {
+        attempts: u16,
+        last: Box<IngrainError>,
+    },
+    /// Retries are configured but the request body, e.g. a stream, cannot be
+    /// cloned for another attempt. Nothing was sent.
+    NonRetryableRequest,
+    /// A configured server URL could not be parsed.
+    InvalidUrl { url: String, reason: String },
+    /// The underlying HTTP client could not be constructed.
+    ClientBuild(reqwest::Error),
+    /// A configured root certificate could not be parsed.
+    InvalidCertificate(reqwest::Error),
+    /// The model is not present in the server's model repository.
+    ModelNotFound { name: String },
+    /// The model exists but the inference server has not loaded it.
+    ModelNotLoaded { name: String },
+    /// The model failed to load or did not become ready in time; `state` is
+    /// the last state seen.
+    ModelNotReady { name: String, state: ModelState },
+    /// A classification result did not have one probability per label.
+    LabelMismatch { labels: usize, probabilities: usize },
+    /// A local image file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The image type could not be determined from its contents or extension.
+    UnknownImageFormat { path: Option<PathBuf> },
+    /// A string did not name a known model library.
+    UnknownModelLibrary(String),
+    /// The model library could not be inferred from the model name.
+    CannotInferLibrary { name: String },
+    /// The call was cancelled through its `CancellationToken`.
+    Cancelled,
+    /// The deadline set with
+    /// [`IngrainClient::with_deadline`](crate::IngrainClient::with_deadline)
+    /// passed before the call, retries included, completed.
+    DeadlineExceeded,
+    /// Embedding rows did not all have the same number of dimensions.
+    RaggedEmbeddings {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The server does not provide this endpoint, e.g. an older release.
+    Unsupported { endpoint: String },
+    /// The server returned a different number of results than inputs sent.
+    UnexpectedResultCount { expected: usize, found: usize },
+    /// No inputs were given; `input` names the kind, `text`, `image` or
+    /// `text or image` when `embed` got neither.
+    EmptyInput { input: &'static str },
+    /// More dimensions were requested than the model produces.
+    InvalidDimensions { requested: u16, max: u64 },
+    /// The server failed too often recently, so the request was not sent.
+    CircuitOpen { server: String },
+    /// Another input sent in the same request failed; its error is the one
+    /// at index `first` of the results.
+    ChunkFailed { first: usize },
+    /// An image input was neither an `http(s)` URL nor a valid image data URI.
+    InvalidImageInput { reason: String },
+    /// With strict responses enabled, the response had a field this crate
+    /// does not know; `path` names it, e.g. `modelStats.0.newField`.
+    UnexpectedField { path: String, body: String },
+    /// The model is loaded, so it was not deleted.
+    ModelInUse { name: String },
+    /// The arguments of a call contradict each other, e.g. image download
+    /// headers without any images. Nothing was sent.
+    InvalidRequest { reason: String },
+}
+
+impl fmt::Display for IngrainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IngrainError::Http {
+                status,
+                detail: Some(detail),
+                ..
+            } => write!(f, "Request failed with status: {}: {}", status, detail),
+            IngrainError::Http {
+                status,
+                body,
+                detail: None,
+            } => write!(f, "Request failed with status: {} (body: {})", status, body),
+            IngrainError::Network(e) => write!(f, "Network error: {}", e),
+            IngrainError::Connect(e) => write!(f, "Failed to connect: {}", e),
+            IngrainError::Timeout(e) => write!(f, "Request timed out: {}", e),
+            IngrainError::Deserialize { source, path, body } => write!(
+                f,
+                "Failed to parse response at {}: {} (body: {})",
+                path,
+                source,
+                snippet(body)
+            ),
+            IngrainError::RetriesExhausted { attempts, last } => {
+                write!(f, "Request failed after {} attempts: {}", attempts, last)
+            }
+            IngrainError::NonRetryableRequest => write!(
+                f,
+                "Request body cannot be cloned, so it cannot be retried; disable retries for this call"
+            ),
+            IngrainError::InvalidUrl { url, reason } => {
+                write!(f, "Invalid server URL '{}': {}", url, reason)
+            }
+            IngrainError::ClientBuild(e) => write!(f, "Failed to build HTTP client: {}", e),
+            IngrainError::InvalidCertificate(e) => write!(f, "Invalid root certificate: {}", e),
+            IngrainError::ModelNotFound { name } => write!(f, "Model not found: {}", name),
+            IngrainError::ModelNotLoaded { name } => write!(f, "Model not loaded: {}", name),
+            IngrainError::ModelNotReady { name, state } => {
+                write!(f, "Model {} not ready (state: {})", name, state)
+            }
+            IngrainError::LabelMismatch {
+                labels,
+                probabilities,
+            } => write!(
+                f,
+                "Model returned {} probabilities for {} labels",
+                probabilities, labels
+            ),
+            IngrainError::Io { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
+            }
+            IngrainError::UnknownImageFormat { path: Some(path) } => {
+                write!(f, "Unknown image format: {}", path.display())
+            }
+            IngrainError::UnknownImageFormat { path: None } => write!(f, "Unknown image format"),
+            IngrainError::UnknownModelLibrary(library) => write!(
+                f,
+                "Unknown model library '{}' (expected open_clip, sentence_transformers or timm)",
+                library
+            ),
+            IngrainError::CannotInferLibrary { name } => write!(
+                f,
+                "Cannot infer the model library of '{}'; pass it to load_model explicitly",
+                name
+            ),
+            IngrainError::Cancelled => write!(f, "Request cancelled"),
+            IngrainError::DeadlineExceeded => write!(f, "Deadline exceeded"),
+            IngrainError::RaggedEmbeddings {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Embedding row {} has {} dimensions, expected {}",
+                row, found, expected
+            ),
+            IngrainError::Unsupported { endpoint } => {
+                write!(f, "The server does not support the {} endpoint", endpoint)
+            }
+            IngrainError::UnexpectedResultCount { expected, found } => write!(
+                f,
+                "Expected {} results from the server but got {}",
+                expected, found
+            ),
+            IngrainError::EmptyInput { input } => write!(f, "No {} inputs were given", input),
+            IngrainError::InvalidDimensions { requested, max } => write!(
+                f,
+                "Requested {} dimensions but the model only produces {}",
+                requested, max
+            ),
+            IngrainError::CircuitOpen { server } => write!(
+                f,
+                "Circuit breaker open for {}; not sending requests until it cools down",
+                server
+            ),
+            IngrainError::ChunkFailed { first } => write!(
+                f,
+                "The request for this input failed; see the error for input {}",
+                first
+            ),
+            IngrainError::InvalidImageInput { reason } => {
+                write!(f, "Invalid image input: {}", reason)
+            }
+            IngrainError::UnexpectedField { path, body } => write!(
+                f,
+                "Unexpected field {} in response (body: {})",
+                path,
+                snippet(body)
+            ),
+            IngrainError::ModelInUse { name } => {
+                write!(f, "Model {} is loaded; unload it before deleting", name)
+            }
+            IngrainError::InvalidRequest { reason } => write!(f, "Invalid request: {}", reason),
+        }
+    }
+}
+
+const BODY_SNIPPET_CHARS: usize = 200;
+
+/// Shortens long bodies, such as full metrics payloads, in error messages.
+fn snippet(body: &str) -> String {
+    match body.char_indices().nth(BODY_SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+impl Error for IngrainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IngrainError::Network(e)
+            | IngrainError::Connect(e)
+            | IngrainError::Timeout(e)
+            | IngrainError::ClientBuild(e)
+            | IngrainError::InvalidCertificate(e) => Some(e),
+            IngrainError::Deserialize { source, .. } => Some(source),
+            IngrainError::RetriesExhausted { last, .. } => Some(last.as_ref()),
+            IngrainError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for IngrainError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            IngrainError::Timeout(e)
+        } else if e.is_connect() {
+            IngrainError::Connect(e)
+        } else {
+            IngrainError::Network(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_truncates_long_bodies() {
+        assert_eq!(snippet("short"), "short");
+
+        let long = "é".repeat(BODY_SNIPPET_CHARS + 10);
+        let shortened = snippet(&long);
+        assert_eq!(shortened.chars().count(), BODY_SNIPPET_CHARS + 3);
+        assert!(shortened.ends_with("..."));
+    }
+}
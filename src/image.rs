@@ -0,0 +1,260 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::Url;
+
+use crate::error::IngrainError;
+
+/// Image formats accepted by [`IngrainClient::embed_image_bytes`](crate::IngrainClient::embed_image_bytes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageMime {
+    Png,
+    Jpeg,
+    Webp,
+    Gif,
+}
+
+impl ImageMime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageMime::Png => "image/png",
+            ImageMime::Jpeg => "image/jpeg",
+            ImageMime::Webp => "image/webp",
+            ImageMime::Gif => "image/gif",
+        }
+    }
+}
+
+/// An image for the inference server to fetch or decode, checked on the
+/// client so that typos and truncated base64 fail before a round-trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageInput {
+    /// An `http` or `https` URL the server downloads.
+    Url(String),
+    /// A `data:image/...;base64,...` URI.
+    DataUri(String),
+}
+
+impl ImageInput {
+    pub fn parse(input: &str) -> Result<ImageInput, IngrainError> {
+        let invalid = |reason: &str| IngrainError::InvalidImageInput {
+            reason: reason.to_string(),
+        };
+
+        if let Some(rest) = input.strip_prefix("data:") {
+            let (media_type, payload) = rest
+                .split_once(";base64,")
+                .ok_or_else(|| invalid("data URI is not base64 encoded"))?;
+            if !media_type.starts_with("image/") {
+                return Err(invalid("data URI does not hold an image"));
+            }
+            if !is_base64(payload) {
+                return Err(invalid("data URI payload is not valid base64"));
+            }
+            return Ok(ImageInput::DataUri(input.to_string()));
+        }
+
+        let url = Url::parse(input).map_err(|e| invalid(&e.to_string()))?;
+        match url.scheme() {
+            "http" | "https" => Ok(ImageInput::Url(input.to_string())),
+            scheme => Err(invalid(&format!(
+                "unsupported scheme '{}' (expected http, https or data)",
+                scheme
+            ))),
+        }
+    }
+
+    /// The string sent to the server.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ImageInput::Url(input) | ImageInput::DataUri(input) => input,
+        }
+    }
+}
+
+impl FromStr for ImageInput {
+    type Err = IngrainError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        ImageInput::parse(input)
+    }
+}
+
+impl fmt::Display for ImageInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<ImageInput> for String {
+    fn from(input: ImageInput) -> String {
+        match input {
+            ImageInput::Url(input) | ImageInput::DataUri(input) => input,
+        }
+    }
+}
+
+/// Checks the shape of padded standard base64 without decoding it.
+fn is_base64(payload: &str) -> bool {
+    let data = payload.trim_end_matches('=');
+    !payload.is_empty()
+        && payload.len().is_multiple_of(4)
+        && payload.len() - data.len() <= 2
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+/// Detects the image MIME type from the file signature.
+pub(crate) fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else {
+        None
+    }
+}
+
+pub(crate) fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "webp" => Some("image/webp"),
+        "gif" => Some("image/gif"),
+        _ => None,
+    }
+}
+
+/// A 1x1 transparent PNG, small enough to send as a dummy input.
+pub(crate) const TINY_PNG: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F, 0x15, 0xC4,
+    0x89, 0x00, 0x00, 0x00, 0x0A, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00, 0x01, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE,
+    0x42, 0x60, 0x82,
+];
+
+pub(crate) fn encode_data_uri(mime: &str, bytes: &[u8]) -> String {
+    format!("data:{};base64,{}", mime, STANDARD.encode(bytes))
+}
+
+/// Encodes an in-memory PNG, JPEG, WebP or GIF image as a
+/// `data:image/...;base64,...` URI, taking the type from its signature.
+/// Fails with [`IngrainError::UnknownImageFormat`] for anything else.
+pub fn to_data_uri(bytes: &[u8]) -> Result<String, IngrainError> {
+    let mime = sniff_mime(bytes).ok_or(IngrainError::UnknownImageFormat { path: None })?;
+    Ok(encode_data_uri(mime, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_mime() {
+        assert_eq!(sniff_mime(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(sniff_mime(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(
+            sniff_mime(b"RIFF\x00\x00\x00\x00WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(sniff_mime(b"GIF89a..."), Some("image/gif"));
+        assert_eq!(sniff_mime(b"not an image"), None);
+    }
+
+    #[test]
+    fn test_mime_from_extension() {
+        assert_eq!(
+            mime_from_extension(Path::new("a/b.JPG")),
+            Some("image/jpeg")
+        );
+        assert_eq!(mime_from_extension(Path::new("b.webp")), Some("image/webp"));
+        assert_eq!(mime_from_extension(Path::new("b.txt")), None);
+        assert_eq!(mime_from_extension(Path::new("b")), None);
+    }
+
+    #[test]
+    fn test_image_mime_strings_match_sniffing() {
+        assert_eq!(
+            ImageMime::Png.as_str(),
+            sniff_mime(b"\x89PNG\r\n\x1a\n").unwrap()
+        );
+        assert_eq!(
+            ImageMime::Jpeg.as_str(),
+            sniff_mime(&[0xFF, 0xD8, 0xFF]).unwrap()
+        );
+        assert_eq!(ImageMime::Webp.as_str(), "image/webp");
+        assert_eq!(ImageMime::Gif.as_str(), "image/gif");
+    }
+
+    #[test]
+    fn test_encode_data_uri() {
+        assert_eq!(
+            encode_data_uri("image/png", b"abc"),
+            "data:image/png;base64,YWJj"
+        );
+    }
+
+    #[test]
+    fn test_to_data_uri_sniffs_format() {
+        let png = to_data_uri(TINY_PNG).unwrap();
+        assert!(png.starts_with("data:image/png;base64,iVBORw0KGgo"));
+        assert!(ImageInput::parse(&png).is_ok());
+
+        let jpeg = to_data_uri(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10]).unwrap();
+        assert_eq!(jpeg, "data:image/jpeg;base64,/9j/4AAQ");
+
+        assert!(matches!(
+            to_data_uri(b"not an image"),
+            Err(IngrainError::UnknownImageFormat { path: None })
+        ));
+    }
+
+    #[test]
+    fn test_image_input_accepts_url() {
+        let input = ImageInput::parse("https://example.com/cat.png").unwrap();
+        assert_eq!(
+            input,
+            ImageInput::Url("https://example.com/cat.png".to_string())
+        );
+    }
+
+    #[test]
+    fn test_image_input_accepts_data_uri() {
+        let uri = encode_data_uri("image/png", TINY_PNG);
+        let input: ImageInput = uri.parse().unwrap();
+        assert_eq!(input.as_str(), uri);
+        assert!(matches!(input, ImageInput::DataUri(_)));
+    }
+
+    #[test]
+    fn test_image_input_rejects_invalid_blobs() {
+        for input in [
+            "data:image/png;base64,iVBORw0KGgo",
+            "data:image/png;base64,",
+            "data:image/png;base64,iVBO*w==",
+            "data:text/plain;base64,YWJj",
+            "data:image/png,raw",
+            "ftp://example.com/cat.png",
+            "cat.png",
+        ] {
+            assert!(
+                matches!(
+                    ImageInput::parse(input),
+                    Err(IngrainError::InvalidImageInput { .. })
+                ),
+                "input {}",
+                input
+            );
+        }
+    }
+}
@@ -1,36 +1,372 @@
-use reqwest::Client;
+use reqwest::header::HeaderValue;
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use std::collections::HashMap;
-use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use futures::{StreamExt, TryStreamExt, stream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+pub mod embeddings;
+pub mod error;
+mod image;
 pub mod models;
+pub mod openai;
+use crate::error::IngrainError;
+pub use crate::image::{ImageInput, ImageMime, to_data_uri};
 use crate::models::{
-    EmbeddingRequest, EmbeddingResponse, GenericMessageResponse, ImageClassificationRequest,
+    Dims, EmbeddingRequest, EmbeddingResponse, GenericMessageResponse, ImageClassificationRequest,
     ImageClassificationResponse, ImageEmbeddingRequest, ImageEmbeddingResponse, LoadModelRequest,
-    LoadedModelResponse, MetricsResponse, ModelClassificationLabelsResponse,
-    ModelEmbeddingDimsResponse, ModelLibrary, ModelMetadataRequest, RepositoryModelResponse,
-    TextEmbeddingRequest, TextEmbeddingResponse, UnloadModelRequest,
+    LoadedModel, LoadedModelResponse, MetricsResponse, ModelClassificationLabelsResponse,
+    ModelEmbeddingDimsResponse, ModelLibrary, ModelMetadata, ModelMetadataRequest, ModelReload,
+    ModelState, ModelStats, RepositoryModelPage, RepositoryModelResponse, RepositoryPageRequest,
+    ServerInfoResponse, TextEmbeddingRequest, TextEmbeddingResponse, UnloadModelRequest,
 };
 
+mod auth;
+pub use crate::auth::AuthConfig;
+use crate::auth::{RedactedHeaders, is_sensitive_header};
+
+mod cache;
+use crate::cache::MetadataCache;
+
+mod circuit;
+use crate::circuit::CircuitBreakers;
+
+mod rate_limit;
+use crate::rate_limit::RateLimiter;
+
+mod telemetry;
+use crate::telemetry::Telemetry;
+pub use crate::telemetry::{ProcessingTimeTotals, TelemetrySink};
+
+#[cfg(feature = "embedding-cache")]
+mod embedding_cache;
+#[cfg(feature = "embedding-cache")]
+use crate::embedding_cache::EmbeddingCache;
+
+mod builder;
+pub use crate::builder::IngrainClientBuilder;
+
+#[cfg(feature = "blocking")]
+mod blocking;
+#[cfg(feature = "blocking")]
+pub use crate::blocking::IngrainBlockingClient;
+
 mod retry;
-use crate::retry::retry;
+pub use crate::retry::{
+    BackoffConfig, MAX_RETRY_AFTER, ResponseMeta, RetryOverride, RetryReport, is_transient_status,
+};
+use crate::retry::{CallContext, RetryConfig, cancelled, deadline_reached, limited, retry};
+
+/// Selects which of the two servers a request is sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerTarget {
+    Model,
+    Inference,
+}
+
+/// How calls are spread over several inference servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancing {
+    /// Always start with the first server, moving on only when it fails.
+    #[default]
+    Failover,
+    /// Start each call on the next server in turn, failing over from there.
+    RoundRobin,
+}
+
+/// How the field names of request bodies are spelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WireFormat {
+    /// `nDims`, `imageDownloadHeaders`, as current servers expect.
+    #[default]
+    CamelCase,
+    /// `n_dims`, `image_download_headers`, for older servers. Only the
+    /// top-level fields are renamed, so header names inside
+    /// `image_download_headers` are sent as given.
+    SnakeCase,
+}
+
+/// Turns `imageDownloadHeaders` into `image_download_headers`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Renames the top-level fields of a JSON object to snake case.
+fn snake_case_fields(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => fields
+            .into_iter()
+            .map(|(name, value)| (snake_case(&name), value))
+            .collect(),
+        other => other,
+    }
+}
+
+/// Per-call settings threaded through to the retry loop.
+#[derive(Default, Clone, Copy)]
+struct RequestOptions<'a> {
+    cancel: Option<&'a CancellationToken>,
+    report: Option<&'a Mutex<RetryReport>>,
+    meta: Option<&'a Mutex<Option<ResponseMeta>>>,
+    /// Sent as `If-None-Match`.
+    if_none_match: Option<&'a str>,
+    /// Whether the body may be gzipped; see `gzip_requests_above`.
+    #[cfg(feature = "compression")]
+    compress_body: bool,
+    /// The inference endpoint reported to the telemetry sink.
+    telemetry_method: Option<&'a str>,
+}
+
+/// Rejects empty inputs before a request the server would refuse is sent.
+fn non_empty(values: Vec<String>, input: &'static str) -> Result<Vec<String>, IngrainError> {
+    if values.is_empty() {
+        return Err(IngrainError::EmptyInput { input });
+    }
+    Ok(values)
+}
+
+/// Rejects text and image inputs to `embed` the server would refuse or
+/// that point to a caller bug, such as image download headers without
+/// images.
+fn check_mixed_inputs(
+    text: Option<&[String]>,
+    image: Option<&[String]>,
+    image_download_headers: Option<&HashMap<String, String>>,
+) -> Result<(), IngrainError> {
+    match (text, image) {
+        (None, None) => Err(IngrainError::EmptyInput {
+            input: "text or image",
+        }),
+        (Some([]), _) => Err(IngrainError::EmptyInput { input: "text" }),
+        (_, Some([])) => Err(IngrainError::EmptyInput { input: "image" }),
+        (_, None) if image_download_headers.is_some() => Err(IngrainError::InvalidRequest {
+            reason: "image_download_headers were given without any image inputs".to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
 
+/// Takes the only embedding of a single-input response.
+fn single(mut embeddings: Vec<Vec<f32>>) -> Result<Vec<f32>, IngrainError> {
+    match embeddings.len() {
+        1 => Ok(embeddings.swap_remove(0)),
+        found => Err(IngrainError::UnexpectedResultCount { expected: 1, found }),
+    }
+}
+
+/// Per-model outcomes of a bulk operation, in the order the models were given.
+pub type ModelResults = Vec<(String, Result<GenericMessageResponse, IngrainError>)>;
+
+/// A response together with how long the call took as seen by the client,
+/// including network time, queueing and retries. Compare with the server's
+/// `processing_time` to see where the time went.
+#[derive(Debug, Clone)]
+pub struct Timed<T> {
+    pub value: T,
+    pub client_elapsed: Duration,
+}
+
+async fn timed<T>(
+    call: impl Future<Output = Result<T, IngrainError>>,
+) -> Result<Timed<T>, IngrainError> {
+    let started = Instant::now();
+    let value = call.await?;
+    Ok(Timed {
+        value,
+        client_elapsed: started.elapsed(),
+    })
+}
+
+#[cfg(feature = "compression")]
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(bytes)
+        .expect("writing to a Vec cannot fail");
+    encoder.finish().expect("writing to a Vec cannot fail")
+}
+
+fn take_meta(meta: Mutex<Option<ResponseMeta>>) -> ResponseMeta {
+    meta.into_inner()
+        .unwrap()
+        .expect("a successful call records its response")
+}
+
+/// Appends the embeddings one chunk of an `embed_batched` call got back,
+/// checking there is one per input sent.
+fn extend_chunk(
+    combined: &mut Option<Vec<Vec<f32>>>,
+    chunk: Option<&[String]>,
+    embeddings: Option<Vec<Vec<f32>>>,
+) -> Result<(), IngrainError> {
+    let expected = chunk.map_or(0, |chunk| chunk.len());
+    let embeddings = embeddings.unwrap_or_default();
+    if embeddings.len() != expected {
+        return Err(IngrainError::UnexpectedResultCount {
+            expected,
+            found: embeddings.len(),
+        });
+    }
+    if let Some(combined) = combined {
+        combined.extend(embeddings);
+    }
+    Ok(())
+}
+
+fn into_strings(values: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+    values.into_iter().map(Into::into).collect()
+}
+
+/// Cloning is cheap: clones share the underlying connection pool.
+/// The `Debug` output never includes auth credentials.
+#[derive(Clone)]
 pub struct IngrainClient {
     model_server_url: String,
-    inference_server_url: String,
+    inference_server_urls: Vec<String>,
+    load_balancing: LoadBalancing,
+    next_server: Arc<AtomicUsize>,
+    /// The last `ETag` seen per endpoint; see
+    /// [`IngrainClient::repository_models_if_changed`].
+    etags: Arc<Mutex<HashMap<String, String>>>,
+    path_prefix: String,
     client: Client,
-    retries: u16,
-    retry_delay_ms: u64,
+    retry: RetryConfig,
+    auth: Option<AuthConfig>,
+    default_headers: HashMap<String, String>,
+    generate_request_ids: bool,
+    request_id: Option<String>,
+    deadline: Option<Instant>,
+    permits: Option<Arc<Semaphore>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    telemetry: Option<Arc<dyn TelemetrySink>>,
+    metadata_cache: Option<Arc<MetadataCache>>,
+    breakers: Option<Arc<CircuitBreakers>>,
+    #[cfg(feature = "embedding-cache")]
+    embedding_cache: Option<Arc<EmbeddingCache>>,
+    auto_load: Option<ModelLibrary>,
+    strict_responses: bool,
+    wire_format: WireFormat,
+    #[cfg(feature = "compression")]
+    gzip_requests_above: Option<usize>,
+}
+
+impl fmt::Debug for IngrainClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IngrainClient")
+            .field("model_server_url", &self.model_server_url)
+            .field("inference_server_urls", &self.inference_server_urls)
+            .field("load_balancing", &self.load_balancing)
+            .field("path_prefix", &self.path_prefix)
+            .field("retry", &self.retry)
+            .field("auth", &self.auth)
+            .field("default_headers", &RedactedHeaders(&self.default_headers))
+            .field("auto_load", &self.auto_load)
+            .field("strict_responses", &self.strict_responses)
+            .finish_non_exhaustive()
+    }
+}
+
+const AUTO_LOAD_TIMEOUT: Duration = Duration::from_secs(300);
+const AUTO_LOAD_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Whether the inference server rejected a call because the model is not
+/// loaded, i.e. its error message says so. A bare 404 is not enough, as a
+/// wrong path prefix or an unknown model gives one too.
+fn is_model_not_loaded(err: &IngrainError) -> bool {
+    match err {
+        IngrainError::Http { body, detail, .. } => detail
+            .as_deref()
+            .unwrap_or(body)
+            .to_ascii_lowercase()
+            .contains("not loaded"),
+        _ => false,
+    }
+}
+
+/// Turns a 4xx response about the model `name` into
+/// [`IngrainError::ModelNotFound`] or [`IngrainError::ModelNotLoaded`] when
+/// its message says which. Any other error is returned unchanged.
+fn model_error(err: IngrainError, name: &str) -> IngrainError {
+    let IngrainError::Http {
+        status,
+        body,
+        detail,
+    } = &err
+    else {
+        return err;
+    };
+    if !status.is_client_error() {
+        return err;
+    }
+    let message = detail.as_deref().unwrap_or(body).to_ascii_lowercase();
+    let name = name.to_string();
+    if message.contains("not loaded") {
+        IngrainError::ModelNotLoaded { name }
+    } else if ["not found", "does not exist", "not in the repository"]
+        .iter()
+        .any(|phrase| message.contains(phrase))
+    {
+        IngrainError::ModelNotFound { name }
+    } else {
+        err
+    }
+}
+
+/// Whether a failed call should be tried on the next inference server: the
+/// host could not be reached, timed out or failed with a 5xx.
+fn is_host_failure(err: &IngrainError) -> bool {
+    match err {
+        IngrainError::Network(_) | IngrainError::Connect(_) | IngrainError::Timeout(_) => true,
+        IngrainError::Http { status, .. } => status.is_server_error(),
+        IngrainError::RetriesExhausted { last, .. } => is_host_failure(last),
+        IngrainError::CircuitOpen { .. } => true,
+        _ => false,
+    }
 }
 
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
 impl IngrainClient {
+    pub fn builder() -> IngrainClientBuilder {
+        IngrainClientBuilder::new()
+    }
+
     pub fn new(model_server_url: &str, inference_server_url: &str) -> Self {
-        IngrainClient {
-            model_server_url: model_server_url.to_string(),
-            inference_server_url: inference_server_url.to_string(),
-            client: Client::new(),
-            retries: 0,
-            retry_delay_ms: 0,
-        }
+        IngrainClient::builder()
+            .model_server_url(model_server_url)
+            .inference_server_url(inference_server_url)
+            .build_unchecked()
+    }
+
+    /// Creates a client that sends requests through the given
+    /// `reqwest::Client`; see [`IngrainClientBuilder::client`].
+    pub fn with_client(model_server_url: &str, inference_server_url: &str, client: Client) -> Self {
+        IngrainClient::builder()
+            .model_server_url(model_server_url)
+            .inference_server_url(inference_server_url)
+            .client(client)
+            .build_unchecked()
     }
 
     pub fn new_with_retries(
@@ -39,368 +375,5312 @@ impl IngrainClient {
         retries: u16,
         retry_delay_ms: u64,
     ) -> Self {
-        IngrainClient {
-            model_server_url: model_server_url.to_string(),
-            inference_server_url: inference_server_url.to_string(),
-            client: Client::new(),
+        IngrainClient::new_with_backoff(
+            model_server_url,
+            inference_server_url,
             retries,
             retry_delay_ms,
-        }
+            BackoffConfig::flat(),
+        )
     }
 
-    async fn server_health(
-        &self,
-        api_url: String,
-    ) -> Result<GenericMessageResponse, Box<dyn Error>> {
-        let response = self.client.get(&api_url).send().await?;
-
-        let status = response.status();
+    pub fn new_with_backoff(
+        model_server_url: &str,
+        inference_server_url: &str,
+        retries: u16,
+        retry_delay_ms: u64,
+        backoff: BackoffConfig,
+    ) -> Self {
+        IngrainClient::builder()
+            .model_server_url(model_server_url)
+            .inference_server_url(inference_server_url)
+            .retries(retries)
+            .retry_delay_ms(retry_delay_ms)
+            .backoff(backoff)
+            .build_unchecked()
+    }
 
-        let parsed_body: GenericMessageResponse = serde_json::from_str(&response.text().await?)?;
+    /// The model server URL, without a trailing slash.
+    pub fn model_server_url(&self) -> &str {
+        &self.model_server_url
+    }
 
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!("Request failed with status: {}", status).into())
-        }
+    /// The first inference server URL, without a trailing slash; see
+    /// [`IngrainClient::inference_server_urls`] for all replicas.
+    pub fn inference_server_url(&self) -> &str {
+        &self.inference_server_urls[0]
     }
 
-    pub async fn model_server_health(&self) -> Result<GenericMessageResponse, Box<dyn Error>> {
-        let api_url = format!("{}/health", self.model_server_url);
-        self.server_health(api_url).await
+    pub fn inference_server_urls(&self) -> &[String] {
+        &self.inference_server_urls
     }
 
-    pub async fn inference_server_health(&self) -> Result<GenericMessageResponse, Box<dyn Error>> {
-        let api_url = format!("{}/health", self.inference_server_url);
-        self.server_health(api_url).await
+    pub fn retries(&self) -> u16 {
+        self.retry.retries
     }
 
-    pub async fn loaded_models(&self) -> Result<LoadedModelResponse, Box<dyn Error>> {
-        let api_url = format!("{}/loaded_models", self.model_server_url);
-        let response = self.client.get(&api_url).send().await?;
-        let status = response.status();
+    pub fn retry_delay_ms(&self) -> u64 {
+        self.retry.retry_delay_ms
+    }
 
-        let parsed_body: LoadedModelResponse = serde_json::from_str(&response.text().await?)?;
+    /// Replaces the predicate deciding which HTTP statuses are retried.
+    /// Defaults to [`is_transient_status`]; network errors are always retried.
+    pub fn with_retry_on(mut self, retry_on: fn(StatusCode) -> bool) -> Self {
+        self.retry.retry_on = retry_on;
+        self
+    }
 
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!("Request failed with status: {}", status).into())
+    /// Returns a client that sends `request_id` in the `X-Request-Id` header
+    /// of every call, overriding generated IDs. Clones are cheap, so this can
+    /// be used per call to correlate it with the server logs:
+    /// `client.with_request_id(&id).embed_text(..)`.
+    pub fn with_request_id(&self, request_id: impl Into<String>) -> IngrainClient {
+        IngrainClient {
+            request_id: Some(request_id.into()),
+            ..self.clone()
         }
     }
 
-    pub async fn repository_models(&self) -> Result<RepositoryModelResponse, Box<dyn Error>> {
-        let api_url = format!("{}/repository_models", self.model_server_url);
-        let response = self.client.get(&api_url).send().await?;
-        let status = response.status();
+    /// Returns a client whose calls, retries and waits for a free slot
+    /// included, fail with [`IngrainError::DeadlineExceeded`] once `deadline`
+    /// has passed. Unlike the retry deadline, which is relative to the start
+    /// of each call, this is a fixed point in time, so several calls can
+    /// share one budget: `client.with_deadline(deadline).embed_text(..)`.
+    pub fn with_deadline(&self, deadline: Instant) -> IngrainClient {
+        IngrainClient {
+            deadline: Some(deadline),
+            ..self.clone()
+        }
+    }
 
-        let parsed_body: RepositoryModelResponse = serde_json::from_str(&response.text().await?)?;
+    /// Returns a client that uses the given retry count and delay instead of
+    /// the configured ones, e.g. for a slow `load_model` call. Backoff,
+    /// `retry_on` and the retry deadline are kept. Clones are cheap and share
+    /// the connection pool and concurrency limit.
+    pub fn with_retry_override(&self, retry_override: RetryOverride) -> IngrainClient {
+        IngrainClient {
+            retry: self.retry.with_override(retry_override),
+            ..self.clone()
+        }
+    }
 
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!("Request failed with status: {}", status).into())
+    /// Returns a client whose embed and classify calls load the model with
+    /// `library` when the server reports it is not loaded, wait for it to
+    /// become ready (for up to five minutes) and then send the call once
+    /// more. Clones are cheap, so this can be used per call.
+    pub fn with_auto_load(&self, library: ModelLibrary) -> IngrainClient {
+        IngrainClient {
+            auto_load: Some(library),
+            ..self.clone()
         }
     }
 
-    pub async fn metrics(&self) -> Result<MetricsResponse, Box<dyn Error>> {
-        let api_url = format!("{}/metrics", self.inference_server_url);
-        let response = self.client.get(&api_url).send().await?;
-        let status = response.status();
+    async fn auto_loading<T, F, Fut>(&self, name: &str, call: F) -> Result<T, IngrainError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, IngrainError>>,
+    {
+        let library = match &self.auto_load {
+            Some(library) => library,
+            None => return call().await.map_err(|e| model_error(e, name)),
+        };
 
-        let parsed_body: MetricsResponse = serde_json::from_str(&response.text().await?)?;
+        let result = match call().await {
+            Err(e) if is_model_not_loaded(&e) => {
+                self.load_model(name, library.clone()).await?;
+                self.wait_for_model(name, AUTO_LOAD_TIMEOUT, AUTO_LOAD_POLL_INTERVAL)
+                    .await?;
+                call().await
+            }
+            result => result,
+        };
+        result.map_err(|e| model_error(e, name))
+    }
 
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!("Request failed with status: {}", status).into())
+    /// Empties the embedding size and label cache enabled with
+    /// [`IngrainClientBuilder::cache_model_metadata`], and the text
+    /// embedding cache when the `embedding-cache` feature is on. Does nothing
+    /// when caching is off.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.metadata_cache {
+            cache.clear();
+        }
+        #[cfg(feature = "embedding-cache")]
+        if let Some(cache) = &self.embedding_cache {
+            cache.clear();
         }
     }
 
-    pub async fn load_model(
-        &self,
-        name: String,
-        library: ModelLibrary,
-    ) -> Result<GenericMessageResponse, Box<dyn Error>> {
-        let api_url = format!("{}/load_model", self.model_server_url);
-
-        let payload = LoadModelRequest { name, library };
-
-        let response = self.client.post(api_url).json(&payload).send().await?;
+    fn invalidate_cache(&self, name: &str) {
+        if let Some(cache) = &self.metadata_cache {
+            cache.invalidate(name);
+        }
+    }
 
-        let status = response.status();
-        let body_text = response.text().await?;
+    /// Generates a request ID in the same format the client uses when
+    /// `generate_request_ids` is enabled.
+    pub fn generate_request_id() -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
 
-        let parsed_body: GenericMessageResponse = serde_json::from_str(&body_text)?;
+    fn request(&self, method: Method, api_url: &str) -> RequestBuilder {
+        let mut request = self.client.request(method, api_url);
+        for (name, value) in &self.default_headers {
+            match HeaderValue::from_str(value) {
+                Ok(mut header_value) => {
+                    header_value.set_sensitive(is_sensitive_header(name));
+                    request = request.header(name.as_str(), header_value);
+                }
+                // Let reqwest surface the invalid value when the request is sent.
+                Err(_) => request = request.header(name.as_str(), value.as_str()),
+            }
+        }
+        let request_id = match &self.request_id {
+            Some(request_id) => Some(request_id.clone()),
+            None => self
+                .generate_request_ids
+                .then(IngrainClient::generate_request_id),
+        };
+        if let Some(request_id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, request_id);
+        }
+        match &self.auth {
+            Some(auth) => auth.apply(request),
+            None => request,
+        }
+    }
 
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!(
-                "Request failed with status: {} and body: {}",
-                status, body_text
-            )
-            .into())
+    /// The model server, or every configured inference server in failover
+    /// order.
+    fn base_urls(&self, server: ServerTarget) -> &[String] {
+        match server {
+            ServerTarget::Model => std::slice::from_ref(&self.model_server_url),
+            ServerTarget::Inference => &self.inference_server_urls,
         }
     }
 
-    pub async fn unload_model(
-        &self,
-        name: String,
-    ) -> Result<GenericMessageResponse, Box<dyn Error>> {
-        let api_url = format!("{}/unload_model", self.model_server_url);
+    fn endpoint_on(&self, base_url: &str, url_suffix: &str) -> String {
+        format!(
+            "{}{}/{}",
+            base_url,
+            self.path_prefix,
+            url_suffix.trim_start_matches('/')
+        )
+    }
 
-        let payload = UnloadModelRequest { name };
+    /// The endpoint on the first configured host of `server`.
+    fn endpoint(&self, server: ServerTarget, url_suffix: &str) -> String {
+        self.endpoint_on(&self.base_urls(server)[0], url_suffix)
+    }
 
-        let response = self.client.post(api_url).json(&payload).send().await?;
+    /// Sends a JSON request to any endpoint of either server and deserializes
+    /// the response, applying the client's auth, default headers and retry
+    /// settings. Useful for endpoints this crate does not wrap yet; with
+    /// `R = serde_json::Value` the response is returned as-is.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn request_json<B, Q, R>(
+        &self,
+        method: Method,
+        url_suffix: &str,
+        server: ServerTarget,
+        body: Option<&B>,
+        query: Option<&Q>,
+    ) -> Result<R, IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        self.send_json(
+            method,
+            url_suffix,
+            server,
+            body,
+            query,
+            &RequestOptions::default(),
+        )
+        .await
+    }
 
-        let status = response.status();
-        let body_text = response.text().await?;
+    /// Like [`IngrainClient::request_json`], also returning the status and
+    /// selected headers of the response.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn request_json_with_meta<B, Q, R>(
+        &self,
+        method: Method,
+        url_suffix: &str,
+        server: ServerTarget,
+        body: Option<&B>,
+        query: Option<&Q>,
+    ) -> Result<(R, ResponseMeta), IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        let meta = Mutex::new(None);
+        let options = RequestOptions {
+            meta: Some(&meta),
+            ..RequestOptions::default()
+        };
+        let response = self
+            .send_json(method, url_suffix, server, body, query, &options)
+            .await?;
+        Ok((response, take_meta(meta)))
+    }
 
-        let parsed_body: GenericMessageResponse = serde_json::from_str(&body_text)?;
+    async fn send_json<B, Q, R>(
+        &self,
+        method: Method,
+        url_suffix: &str,
+        server: ServerTarget,
+        body: Option<&B>,
+        query: Option<&Q>,
+        options: &RequestOptions<'_>,
+    ) -> Result<R, IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        #[cfg(feature = "compression")]
+        let options = &RequestOptions {
+            compress_body: server == ServerTarget::Inference,
+            ..*options
+        };
+        let options = &RequestOptions {
+            telemetry_method: (server == ServerTarget::Inference).then_some(url_suffix),
+            ..*options
+        };
 
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!(
-                "Request failed with status: {} and body: {}",
-                status, body_text
-            )
-            .into())
+        // Each host gets the full retry budget before the next one is tried.
+        let base_urls = self.base_urls(server);
+        let start = match self.load_balancing {
+            LoadBalancing::RoundRobin if base_urls.len() > 1 => {
+                self.next_server.fetch_add(1, Ordering::Relaxed) % base_urls.len()
+            }
+            _ => 0,
+        };
+        let mut last_err = None;
+        for offset in 0..base_urls.len() {
+            let base_url = &base_urls[(start + offset) % base_urls.len()];
+            let url = self.endpoint_on(base_url, url_suffix);
+            let result = self
+                .send_json_through_breaker(base_url, method.clone(), &url, body, query, options)
+                .await;
+            match result {
+                Err(e) if base_urls.len() > 1 && is_host_failure(&e) => last_err = Some(e),
+                result => return result,
+            }
         }
+        Err(last_err.expect("at least one server URL is configured"))
     }
 
-    pub async fn delete_model(
+    async fn send_json_through_breaker<B, Q, R>(
         &self,
-        name: String,
-    ) -> Result<GenericMessageResponse, Box<dyn Error>> {
-        let api_url = format!("{}/delete_model", self.model_server_url);
-
-        let payload = UnloadModelRequest { name };
-
-        let response = self.client.post(api_url).json(&payload).send().await?;
-
-        let status = response.status();
-        let body_text = response.text().await?;
-
-        let parsed_body: GenericMessageResponse = serde_json::from_str(&body_text)?;
-
-        if status.is_success() {
-            Ok(parsed_body)
-        } else {
-            Err(format!(
-                "Request failed with status: {} and body: {}",
-                status, body_text
-            )
-            .into())
+        base_url: &str,
+        method: Method,
+        url: &str,
+        body: Option<&B>,
+        query: Option<&Q>,
+        options: &RequestOptions<'_>,
+    ) -> Result<R, IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        let Some(breakers) = &self.breakers else {
+            return self.send_json_to(method, url, body, query, options).await;
+        };
+        breakers.check(base_url)?;
+        let result = self.send_json_to(method, url, body, query, options).await;
+        match &result {
+            Err(IngrainError::Cancelled | IngrainError::DeadlineExceeded) => {
+                breakers.record_abandoned(base_url)
+            }
+            Err(e) if is_host_failure(e) => breakers.record_failure(base_url),
+            _ => breakers.record_success(base_url),
         }
+        result
     }
 
-    pub async fn embed_text(
+    async fn send_json_to<B, Q, R>(
         &self,
-        name: String,
-        text: Vec<String>,
-        normalize: Option<bool>,
-        n_dims: Option<u16>,
-    ) -> Result<TextEmbeddingResponse, Box<dyn Error>> {
-        let api_url = format!("{}/embed_text", self.inference_server_url);
+        method: Method,
+        url: &str,
+        body: Option<&B>,
+        query: Option<&Q>,
+        options: &RequestOptions<'_>,
+    ) -> Result<R, IngrainError>
+    where
+        B: Serialize + ?Sized,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("request", method = %method, url = %url);
+        #[cfg(feature = "log")]
+        log::debug!("sending {} {}", method, url);
 
-        let payload = TextEmbeddingRequest {
-            text,
-            normalize,
-            n_dims,
-            name,
+        let mut request = self.request(method, url);
+        if let Some(body) = body {
+            request = self.json_body(request, body, options);
+        }
+        if let Some(query) = query {
+            request = request.query(query);
+        }
+        if let Some(etag) = options.if_none_match {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        let context = CallContext {
+            cancel: options.cancel,
+            permits: self.permits.as_deref(),
+            rate_limiter: self.rate_limiter.as_deref(),
+            deadline: self.deadline,
+            report: options.report,
+            meta: options.meta,
+            strict: self.strict_responses,
+            telemetry: self
+                .telemetry
+                .as_deref()
+                .zip(options.telemetry_method)
+                .map(|(sink, method)| Telemetry { sink, method }),
         };
+        let response = retry(request, &self.retry, &context);
 
-        let request = self.client.post(api_url).json(&payload);
+        #[cfg(feature = "tracing")]
+        let response = tracing::Instrument::instrument(response, span);
+        response.await
+    }
 
-        let response: TextEmbeddingResponse =
-            retry(request, self.retries, self.retry_delay_ms).await?;
-        Ok(response)
+    fn json_body<B>(
+        &self,
+        request: RequestBuilder,
+        body: &B,
+        options: &RequestOptions<'_>,
+    ) -> RequestBuilder
+    where
+        B: Serialize + ?Sized,
+    {
+        // A body that fails to serialize is left for reqwest to report.
+        if self.wire_format == WireFormat::SnakeCase
+            && let Ok(value) = serde_json::to_value(body)
+        {
+            return self.encode_json_body(request, &snake_case_fields(value), options);
+        }
+        self.encode_json_body(request, body, options)
     }
 
-    pub async fn embed_image(
+    #[cfg_attr(not(feature = "compression"), allow(unused_variables))]
+    fn encode_json_body<B>(
         &self,
-        name: String,
-        image: Vec<String>,
-        normalize: Option<bool>,
-        n_dims: Option<u16>,
-        image_download_headers: Option<HashMap<String, String>>,
-    ) -> Result<ImageEmbeddingResponse, Box<dyn Error>> {
-        let api_url = format!("{}/embed_image", self.inference_server_url);
+        request: RequestBuilder,
+        body: &B,
+        options: &RequestOptions<'_>,
+    ) -> RequestBuilder
+    where
+        B: Serialize + ?Sized,
+    {
+        #[cfg(feature = "compression")]
+        if let Some(min_bytes) = self.gzip_requests_above.filter(|_| options.compress_body)
+            && let Ok(json) = serde_json::to_vec(body)
+            && json.len() >= min_bytes
+        {
+            return request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                .body(gzip(&json));
+        }
+        request.json(body)
+    }
 
-        let payload = ImageEmbeddingRequest {
-            image,
-            normalize,
-            n_dims,
-            name,
-            image_download_headers,
+    async fn get_json_if_changed<R>(
+        &self,
+        url_suffix: &str,
+        server: ServerTarget,
+    ) -> Result<Option<R>, IngrainError>
+    where
+        R: DeserializeOwned + Send + 'static,
+    {
+        let key = self.endpoint(server, url_suffix);
+        let etag = self.etags.lock().unwrap().get(&key).cloned();
+        let meta = Mutex::new(None);
+        let options = RequestOptions {
+            meta: Some(&meta),
+            if_none_match: etag.as_deref(),
+            ..RequestOptions::default()
         };
+        let result = self
+            .send_json::<(), (), R>(Method::GET, url_suffix, server, None, None, &options)
+            .await;
+        match result {
+            Ok(response) => {
+                let mut etags = self.etags.lock().unwrap();
+                match take_meta(meta).etag {
+                    Some(etag) => etags.insert(key, etag),
+                    None => etags.remove(&key),
+                };
+                Ok(Some(response))
+            }
+            Err(IngrainError::Http { status, .. }) if status == StatusCode::NOT_MODIFIED => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
 
-        let request = self.client.post(api_url).json(&payload);
-
-        let response: ImageEmbeddingResponse =
-            retry(request, self.retries, self.retry_delay_ms).await?;
-        Ok(response)
+    async fn get_json<R>(&self, url_suffix: &str, server: ServerTarget) -> Result<R, IngrainError>
+    where
+        R: DeserializeOwned + Send + 'static,
+    {
+        self.request_json::<(), (), R>(Method::GET, url_suffix, server, None, None)
+            .await
     }
 
-    pub async fn embed(
+    async fn post_json<B, R>(
         &self,
-        name: String,
-        text: Option<Vec<String>>,
-        image: Option<Vec<String>>,
-        normalize: Option<bool>,
-        n_dims: Option<u16>,
-        image_download_headers: Option<HashMap<String, String>>,
-    ) -> Result<EmbeddingResponse, Box<dyn Error>> {
-        if text.is_none() && image.is_none() {
-            return Ok(EmbeddingResponse {
-                text_embeddings: None,
-                image_embeddings: None,
-                processing_time_ms: 0.0f32,
-            });
-        }
+        url_suffix: &str,
+        server: ServerTarget,
+        body: &B,
+    ) -> Result<R, IngrainError>
+    where
+        B: Serialize + ?Sized,
+        R: DeserializeOwned + Send + 'static,
+    {
+        self.request_json(Method::POST, url_suffix, server, Some(body), None::<&()>)
+            .await
+    }
 
-        let api_url = format!("{}/embed", self.inference_server_url);
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_server_health(&self) -> Result<GenericMessageResponse, IngrainError> {
+        self.get_json("health", ServerTarget::Model).await
+    }
 
-        let payload = EmbeddingRequest {
-            image,
-            text,
-            normalize,
-            n_dims,
-            name,
-            image_download_headers,
-        };
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn inference_server_health(&self) -> Result<GenericMessageResponse, IngrainError> {
+        self.get_json("health", ServerTarget::Inference).await
+    }
 
-        let request = self.client.post(api_url).json(&payload);
+    /// Whether the model server is ready to serve requests. Any non-success
+    /// status counts as not ready; only failing to reach the server is an
+    /// error. With several inference server URLs configured, the inference
+    /// server probes check every one and report `true` only when all pass.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_server_ready(&self) -> Result<bool, IngrainError> {
+        self.probe(ServerTarget::Model, "ready").await
+    }
 
-        let response: EmbeddingResponse = retry(request, self.retries, self.retry_delay_ms).await?;
-        Ok(response)
+    /// Whether the model server process is up; see
+    /// [`IngrainClient::model_server_ready`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_server_live(&self) -> Result<bool, IngrainError> {
+        self.probe(ServerTarget::Model, "live").await
     }
 
-    pub async fn classify_image(
-        &self,
-        name: String,
-        image: Vec<String>,
-        image_download_headers: Option<HashMap<String, String>>,
-    ) -> Result<ImageClassificationResponse, Box<dyn Error>> {
-        let api_url = format!("{}/classify_image", self.inference_server_url);
+    /// Whether the inference server is ready to serve requests; see
+    /// [`IngrainClient::model_server_ready`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn inference_server_ready(&self) -> Result<bool, IngrainError> {
+        self.probe(ServerTarget::Inference, "ready").await
+    }
 
-        let payload = ImageClassificationRequest {
-            image,
-            name,
-            image_download_headers,
-        };
+    /// Whether the inference server process is up; see
+    /// [`IngrainClient::model_server_ready`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn inference_server_live(&self) -> Result<bool, IngrainError> {
+        self.probe(ServerTarget::Inference, "live").await
+    }
 
-        let request = self.client.post(api_url).json(&payload);
+    /// Probes every host of `server` concurrently. Probes are sent once,
+    /// without retries, and their body is ignored.
+    async fn probe(&self, server: ServerTarget, url_suffix: &str) -> Result<bool, IngrainError> {
+        let probes = self
+            .base_urls(server)
+            .iter()
+            .map(|base_url| self.probe_host(base_url, url_suffix));
+        let ready = futures::future::join_all(probes)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<bool>, IngrainError>>()?;
+        Ok(ready.into_iter().all(|ready| ready))
+    }
 
-        let response: ImageClassificationResponse =
-            retry(request, self.retries, self.retry_delay_ms).await?;
-        Ok(response)
+    async fn probe_host(&self, base_url: &str, url_suffix: &str) -> Result<bool, IngrainError> {
+        if let Some(breakers) = &self.breakers {
+            breakers.check(base_url)?;
+        }
+        let request = self.request(Method::GET, &self.endpoint_on(base_url, url_suffix));
+        let sent = limited(
+            self.permits.as_deref(),
+            self.rate_limiter.as_deref(),
+            request.send(),
+        );
+        let result = tokio::select! {
+            biased;
+            _ = deadline_reached(self.deadline) => Err(IngrainError::DeadlineExceeded),
+            response = sent => response.map_err(IngrainError::from),
+        };
+        if let Some(breakers) = &self.breakers {
+            match &result {
+                Err(IngrainError::DeadlineExceeded) => breakers.record_abandoned(base_url),
+                Err(e) if is_host_failure(e) => breakers.record_failure(base_url),
+                Ok(response) if response.status().is_server_error() => {
+                    breakers.record_failure(base_url)
+                }
+                _ => breakers.record_success(base_url),
+            }
+        }
+        Ok(result?.status().is_success())
     }
 
-    pub async fn model_classification_labels(
+    /// Checks both servers concurrently, returning the model server result
+    /// first and the inference server result second.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn health_all(
         &self,
-        name: String,
-    ) -> Result<ModelClassificationLabelsResponse, Box<dyn Error>> {
-        let api_url = format!("{}/model_classification_labels", self.model_server_url);
+    ) -> (
+        Result<GenericMessageResponse, IngrainError>,
+        Result<GenericMessageResponse, IngrainError>,
+    ) {
+        tokio::join!(self.model_server_health(), self.inference_server_health())
+    }
 
-        let payload = ModelMetadataRequest { name };
+    /// Returns the model server's version. Servers that predate the `/info`
+    /// endpoint fail with [`IngrainError::Unsupported`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_server_info(&self) -> Result<ServerInfoResponse, IngrainError> {
+        self.get_json("info", ServerTarget::Model)
+            .await
+            .map_err(|e| match e {
+                IngrainError::Http { status, .. } if status == StatusCode::NOT_FOUND => {
+                    IngrainError::Unsupported {
+                        endpoint: "/info".to_string(),
+                    }
+                }
+                e => e,
+            })
+    }
 
-        let request = self.client.get(api_url).query(&payload);
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn loaded_models(&self) -> Result<LoadedModelResponse, IngrainError> {
+        self.get_json("loaded_models", ServerTarget::Model).await
+    }
 
-        let response: ModelClassificationLabelsResponse =
-            retry(request, self.retries, self.retry_delay_ms).await?;
-        Ok(response)
+    /// Looks up a single model in `loaded_models`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn loaded_model(&self, name: &str) -> Result<Option<LoadedModel>, IngrainError> {
+        let loaded = self.loaded_models().await?;
+        Ok(loaded.models.into_iter().find(|m| m.name == name))
     }
 
-    pub async fn model_embedding_size(
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn loaded_models_by_library(
         &self,
-        name: String,
-    ) -> Result<ModelEmbeddingDimsResponse, Box<dyn Error>> {
-        let api_url = format!("{}/model_embedding_size", self.model_server_url);
+        library: ModelLibrary,
+    ) -> Result<Vec<LoadedModel>, IngrainError> {
+        let loaded = self.loaded_models().await?;
+        Ok(loaded
+            .models
+            .into_iter()
+            .filter(|m| m.library == library)
+            .collect())
+    }
 
-        let payload = ModelMetadataRequest { name };
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn is_model_loaded(&self, name: &str) -> Result<bool, IngrainError> {
+        Ok(self.loaded_model(name).await?.is_some())
+    }
 
-        let request = self.client.get(api_url).query(&payload);
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn repository_models(&self) -> Result<RepositoryModelResponse, IngrainError> {
+        self.get_json("repository_models", ServerTarget::Model)
+            .await
+    }
 
-        let response: ModelEmbeddingDimsResponse =
-            retry(request, self.retries, self.retry_delay_ms).await?;
-        Ok(response)
+    /// Like [`IngrainClient::repository_models`], but sends the `ETag` of
+    /// the last list this client fetched as `If-None-Match` and returns
+    /// `None` when the server replies 304 Not Modified. Clones of the client
+    /// share the stored `ETag`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn repository_models_if_changed(
+        &self,
+    ) -> Result<Option<RepositoryModelResponse>, IngrainError> {
+        self.get_json_if_changed("repository_models", ServerTarget::Model)
+            .await
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use httpmock::Method::{GET, POST};
-    use httpmock::MockServer;
-    use tokio;
+    /// Fetches one page of the model repository, sending `limit` and
+    /// `offset` as query parameters. Pass the returned `next_offset` as the
+    /// next `offset` to walk the whole repository.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn repository_models_paged(
+        &self,
+        page: RepositoryPageRequest,
+    ) -> Result<RepositoryModelPage, IngrainError> {
+        self.request_json(
+            Method::GET,
+            "repository_models",
+            ServerTarget::Model,
+            None::<&()>,
+            Some(&page),
+        )
+        .await
+    }
 
-    #[tokio::test]
-    async fn test_model_server_health_success() {
-        let server = MockServer::start();
+    /// Polls `repository_models` until the named model reports `READY`.
+    ///
+    /// Fails with [`IngrainError::ModelNotFound`] as soon as the model is
+    /// missing from the repository, and with [`IngrainError::ModelNotReady`]
+    /// as soon as it reports `FAILED` or `UNAVAILABLE`, or if it is still not
+    /// ready once `timeout` has elapsed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn wait_for_model(
+        &self,
+        name: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<(), IngrainError> {
+        let deadline = Instant::now() + timeout;
 
-        let mock = server.mock(|when, then| {
-            when.method(GET).path("/health");
-            then.status(200)
-                .header("Content-Type", "application/json")
-                .body(r#"{"message": "Model server healthy"}"#);
-        });
+        loop {
+            let repository = self.repository_models().await?;
+            let model = match repository.models.into_iter().find(|m| m.name == name) {
+                Some(model) => model,
+                None => {
+                    return Err(IngrainError::ModelNotFound {
+                        name: name.to_string(),
+                    });
+                }
+            };
 
-        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+            if model.is_ready() {
+                return Ok(());
+            }
 
-        let response = client.model_server_health().await.unwrap();
+            let failed = matches!(model.state, ModelState::Failed | ModelState::Unavailable);
+            if failed || Instant::now() + poll_interval > deadline {
+                return Err(IngrainError::ModelNotReady {
+                    name: name.to_string(),
+                    state: model.state,
+                });
+            }
 
-        assert_eq!(response.message, "Model server healthy");
+            sleep(poll_interval).await;
+        }
+    }
 
-        mock.assert();
+    /// Like [`IngrainClient::wait_for_model`], returning
+    /// [`IngrainError::Cancelled`] as soon as `token` is cancelled.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn wait_for_model_cancellable(
+        &self,
+        name: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+        token: &CancellationToken,
+    ) -> Result<(), IngrainError> {
+        tokio::select! {
+            biased;
+            _ = cancelled(Some(token)) => Err(IngrainError::Cancelled),
+            result = self.wait_for_model(name, timeout, poll_interval) => result,
+        }
     }
 
-    #[tokio::test]
-    async fn test_model_server_health_failure() {
-        let server = MockServer::start();
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn metrics(&self) -> Result<MetricsResponse, IngrainError> {
+        self.get_json("metrics", ServerTarget::Inference).await
+    }
 
-        let mock = server.mock(|when, then| {
-            when.method(GET).path("/health");
-            then.status(500)
-                .header("Content-Type", "application/json")
-                .body(r#"{"message": "Internal Server Error"}"#);
-        });
+    /// Polls `metrics` every `interval`, starting immediately. Failed polls
+    /// are yielded as errors without ending the stream, so it only ends when
+    /// dropped. Ticks missed because a poll was slow are not made up for.
+    #[cfg(feature = "stream")]
+    pub fn metrics_stream(
+        &self,
+        interval: Duration,
+    ) -> impl futures::Stream<Item = Result<MetricsResponse, IngrainError>> + use<> {
+        let client = self.clone();
+        let mut ticks = tokio::time::interval(interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
-        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+        tokio_stream::wrappers::IntervalStream::new(ticks).then(move |_| {
+            let client = client.clone();
+            async move { client.metrics().await }
+        })
+    }
 
-        let result = client.model_server_health().await;
+    /// Returns the metrics of a single model, or `None` if the server has no
+    /// stats for it. The server cannot filter metrics, so all of them are
+    /// fetched and filtered here.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn metrics_for(&self, name: &str) -> Result<Option<ModelStats>, IngrainError> {
+        let metrics = self.metrics().await?;
+        Ok(metrics
+            .model_stats
+            .into_iter()
+            .find(|stats| stats.name == name))
+    }
+
+    /// Pairs every loaded model with its metrics, or `None` when the server
+    /// has no stats for it, fetching both lists concurrently.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn loaded_models_with_metrics(
+        &self,
+    ) -> Result<Vec<(LoadedModel, Option<ModelStats>)>, IngrainError> {
+        let (loaded, metrics) = tokio::try_join!(self.loaded_models(), self.metrics())?;
+        let mut stats: HashMap<String, ModelStats> = metrics
+            .model_stats
+            .into_iter()
+            .map(|stats| (stats.name.clone(), stats))
+            .collect();
+        Ok(loaded
+            .models
+            .into_iter()
+            .map(|model| {
+                let stats = stats.remove(&model.name);
+                (model, stats)
+            })
+            .collect())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn load_model(
+        &self,
+        name: impl Into<String>,
+        library: ModelLibrary,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        let payload = LoadModelRequest {
+            name: name.into(),
+            library,
+        };
+
+        self.post_json("load_model", ServerTarget::Model, &payload)
+            .await
+            .map_err(|e| model_error(e, &payload.name))
+    }
+
+    /// Loads a model, inferring its library with
+    /// [`ModelLibrary::infer_from_name`]. Fails with
+    /// [`IngrainError::CannotInferLibrary`] without sending a request when the
+    /// name does not follow a known convention.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn load_model_inferred(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        let name = name.into();
+        match ModelLibrary::infer_from_name(&name) {
+            Some(library) => self.load_model(name, library).await,
+            None => Err(IngrainError::CannotInferLibrary { name }),
+        }
+    }
+
+    /// Sends one tiny inference request to a loaded model so its lazy
+    /// initialization happens now rather than on the first real call, and
+    /// returns how long the request took. Timm models are sent a 1x1 image to
+    /// classify; every other library a short text to embed. The embedding
+    /// cache is bypassed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn warmup_model(
+        &self,
+        name: impl Into<String>,
+        library: ModelLibrary,
+    ) -> Result<Duration, IngrainError> {
+        let name = name.into();
+        let started = Instant::now();
+        match library {
+            ModelLibrary::Timm => {
+                let image = image::encode_data_uri(ImageMime::Png.as_str(), image::TINY_PNG);
+                self.classify_image(name, [image], None).await?;
+            }
+            _ => {
+                self.embed_text_inner::<TextEmbeddingResponse>(
+                    name,
+                    ["warmup"],
+                    None,
+                    None,
+                    &RequestOptions::default(),
+                )
+                .await?;
+            }
+        }
+        Ok(started.elapsed())
+    }
+
+    /// Loads several models with up to `concurrency` loads in flight. Every
+    /// model gets its own result, in input order, so one failed load does not
+    /// stop the others. A `concurrency` of zero is treated as one.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn load_models(
+        &self,
+        models: Vec<(String, ModelLibrary)>,
+        concurrency: usize,
+    ) -> ModelResults {
+        stream::iter(models)
+            .map(|(name, library)| async move {
+                let result = self.load_model(name.clone(), library).await;
+                (name, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn unload_model(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        let payload = UnloadModelRequest { name: name.into() };
+        self.invalidate_cache(&payload.name);
+
+        self.post_json("unload_model", ServerTarget::Model, &payload)
+            .await
+            .map_err(|e| model_error(e, &payload.name))
+    }
+
+    /// Unloads the model if it is loaded and loads it again, e.g. to pick up
+    /// a newly downloaded version. With `ready_timeout` set, also waits for
+    /// the model to become ready; see [`IngrainClient::wait_for_model`]. The
+    /// model is unavailable between the two steps.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn reload_model(
+        &self,
+        name: impl Into<String>,
+        library: ModelLibrary,
+        ready_timeout: Option<Duration>,
+    ) -> Result<ModelReload, IngrainError> {
+        let name = name.into();
+        let unload = match self.unload_model(name.clone()).await {
+            Ok(response) => Some(response),
+            Err(IngrainError::ModelNotLoaded { .. }) => None,
+            Err(e) if is_model_not_loaded(&e) => None,
+            Err(e) => return Err(e),
+        };
+        let load = self.load_model(name.clone(), library).await?;
+        if let Some(timeout) = ready_timeout {
+            self.wait_for_model(&name, timeout, AUTO_LOAD_POLL_INTERVAL)
+                .await?;
+        }
+        Ok(ModelReload { unload, load })
+    }
+
+    /// Unloads every currently loaded model. Every model gets its own result
+    /// so one failed unload does not stop the others; only a failure to list
+    /// the loaded models is returned as an error.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn unload_all_models(&self) -> Result<ModelResults, IngrainError> {
+        let loaded = self.loaded_models().await?;
+        let unloads = loaded.models.into_iter().map(|model| async move {
+            let result = self.unload_model(model.name.clone()).await;
+            (model.name, result)
+        });
+        Ok(futures::future::join_all(unloads).await)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn delete_model(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        let payload = UnloadModelRequest { name: name.into() };
+        self.invalidate_cache(&payload.name);
+
+        self.post_json("delete_model", ServerTarget::Model, &payload)
+            .await
+            .map_err(|e| model_error(e, &payload.name))
+    }
+
+    /// Like [`IngrainClient::delete_model`], but first checks
+    /// `loaded_models` and refuses with [`IngrainError::ModelInUse`] while the
+    /// model is loaded, guarding against deleting a model still in use.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn delete_model_if_unloaded(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<GenericMessageResponse, IngrainError> {
+        let name = name.into();
+        if self.is_model_loaded(&name).await? {
+            return Err(IngrainError::ModelInUse { name });
+        }
+        self.delete_model(name).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        self.embed_text_cached(name, text, normalize, n_dims, &RequestOptions::default())
+            .await
+    }
+
+    /// Like [`IngrainClient::embed_text`], also measuring the wall-clock
+    /// time of the whole call on the client, retries included.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_timed(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<Timed<TextEmbeddingResponse>, IngrainError> {
+        timed(self.embed_text(name, text, normalize, n_dims)).await
+    }
+
+    /// Like [`IngrainClient::embed_text`], also returning how many attempts
+    /// the call took and the errors of the attempts that were retried.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_with_report(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> (Result<TextEmbeddingResponse, IngrainError>, RetryReport) {
+        let report = Mutex::new(RetryReport::default());
+        let options = RequestOptions {
+            report: Some(&report),
+            ..RequestOptions::default()
+        };
+        let result = self
+            .embed_text_cached(name, text, normalize, n_dims, &options)
+            .await;
+        (result, report.into_inner().unwrap())
+    }
+
+    /// Like [`IngrainClient::embed_text`], also returning the status and
+    /// selected headers of the response. Bypasses the embedding cache.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_with_meta(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<(TextEmbeddingResponse, ResponseMeta), IngrainError> {
+        let meta = Mutex::new(None);
+        let options = RequestOptions {
+            meta: Some(&meta),
+            ..RequestOptions::default()
+        };
+        let response = self
+            .embed_text_inner(name, text, normalize, n_dims, &options)
+            .await?;
+        Ok((response, take_meta(meta)))
+    }
+
+    /// Embeds a single text and returns its embedding.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_one(
+        &self,
+        name: impl Into<String>,
+        text: impl Into<String>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<Vec<f32>, IngrainError> {
+        let response = self
+            .embed_text(name, [text.into()], normalize, n_dims)
+            .await?;
+        single(response.embeddings)
+    }
+
+    /// Like [`IngrainClient::embed_text`], returning
+    /// [`IngrainError::Cancelled`] as soon as `token` is cancelled, including
+    /// while waiting between retries.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_cancellable(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        token: &CancellationToken,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        let options = RequestOptions {
+            cancel: Some(token),
+            ..RequestOptions::default()
+        };
+        self.embed_text_cached(name, text, normalize, n_dims, &options)
+            .await
+    }
+
+    /// Like [`IngrainClient::embed_text`], but first checks `n_dims` against
+    /// the model's embedding size and fails with
+    /// [`IngrainError::InvalidDimensions`] instead of sending a request the
+    /// server would reject. Requesting fewer dimensions is always allowed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_checked(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        let name = name.into();
+        if let Some(requested) = n_dims.map(Dims::get) {
+            let max = self
+                .model_embedding_size(name.clone())
+                .await?
+                .embedding_size;
+            if u64::from(requested) > max {
+                return Err(IngrainError::InvalidDimensions { requested, max });
+            }
+        }
+        self.embed_text(name, text, normalize, n_dims).await
+    }
+
+    /// Like [`IngrainClient::embed_text`], but returns the response JSON
+    /// without deserializing it into [`TextEmbeddingResponse`], so fields
+    /// this crate does not model yet are kept.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_raw(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<serde_json::Value, IngrainError> {
+        self.embed_text_inner(name, text, normalize, n_dims, &RequestOptions::default())
+            .await
+    }
+
+    async fn embed_text_inner<R>(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        options: &RequestOptions<'_>,
+    ) -> Result<R, IngrainError>
+    where
+        R: DeserializeOwned + Send + 'static,
+    {
+        let payload = TextEmbeddingRequest {
+            text: non_empty(into_strings(text), "text")?,
+            normalize,
+            n_dims,
+            name: name.into(),
+        };
+
+        self.auto_loading(&payload.name, || {
+            self.send_json(
+                Method::POST,
+                "embed_text",
+                ServerTarget::Inference,
+                Some(&payload),
+                None::<&()>,
+                options,
+            )
+        })
+        .await
+    }
+
+    /// Serves what it can from the embedding cache, if enabled, and only
+    /// sends the remaining texts to the server. `processing_time_ms` is that
+    /// of the request for the misses, or zero when every text was cached.
+    async fn embed_text_cached(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        options: &RequestOptions<'_>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        #[cfg(feature = "embedding-cache")]
+        if let Some(cache) = &self.embedding_cache {
+            let name = name.into();
+            let text = non_empty(into_strings(text), "text")?;
+            let mut embeddings: Vec<Option<Vec<f32>>> = text
+                .iter()
+                .map(|text| cache.get(&name, normalize, n_dims, text))
+                .collect();
+            let misses: Vec<String> = text
+                .iter()
+                .zip(&embeddings)
+                .filter(|(_, cached)| cached.is_none())
+                .map(|(text, _)| text.clone())
+                .collect();
+
+            let mut processing_time_ms = 0.0;
+            if !misses.is_empty() {
+                let response: TextEmbeddingResponse = self
+                    .embed_text_inner(name.as_str(), misses.clone(), normalize, n_dims, options)
+                    .await?;
+                if response.embeddings.len() != misses.len() {
+                    return Err(IngrainError::UnexpectedResultCount {
+                        expected: misses.len(),
+                        found: response.embeddings.len(),
+                    });
+                }
+                processing_time_ms = response.processing_time_ms;
+
+                let mut fetched = misses.iter().zip(response.embeddings);
+                for slot in embeddings.iter_mut().filter(|cached| cached.is_none()) {
+                    let (text, embedding) = fetched.next().expect("one embedding per miss");
+                    cache.insert(&name, normalize, n_dims, text, embedding.clone());
+                    *slot = Some(embedding);
+                }
+            }
+            return Ok(TextEmbeddingResponse {
+                embeddings: embeddings.into_iter().flatten().collect(),
+                processing_time_ms,
+            });
+        }
+        self.embed_text_inner(name, text, normalize, n_dims, options)
+            .await
+    }
+
+    /// Embeds `text` in chunks of `batch_size`, running up to `concurrency`
+    /// requests at once. Embeddings are returned in input order and the
+    /// processing times of all chunks are summed. A `batch_size` or
+    /// `concurrency` of zero is treated as one. Fails with
+    /// [`IngrainError::EmptyInput`] when `text` is empty.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_batched(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        batch_size: usize,
+        concurrency: usize,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<TextEmbeddingResponse, IngrainError> {
+        let name = name.into();
+        let text = non_empty(into_strings(text), "text")?;
+        let chunks: Vec<&[String]> = text.chunks(batch_size.max(1)).collect();
+
+        let responses: Vec<TextEmbeddingResponse> = stream::iter(&chunks)
+            .map(|chunk| self.embed_text(name.clone(), chunk.to_vec(), normalize, n_dims))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let mut combined = TextEmbeddingResponse {
+            embeddings: Vec::with_capacity(text.len()),
+            processing_time_ms: 0.0,
+        };
+        for (chunk, response) in chunks.iter().zip(responses) {
+            if response.embeddings.len() != chunk.len() {
+                return Err(IngrainError::UnexpectedResultCount {
+                    expected: chunk.len(),
+                    found: response.embeddings.len(),
+                });
+            }
+            combined.embeddings.extend(response.embeddings);
+            combined.processing_time_ms += response.processing_time_ms;
+        }
+        Ok(combined)
+    }
+
+    /// Like [`IngrainClient::embed_text_batched`], but a failed chunk does
+    /// not fail the whole call: every input gets its own result, in input
+    /// order. The first input of a failed chunk holds the chunk's error and
+    /// the others [`IngrainError::ChunkFailed`] pointing at it, so only the
+    /// `Err` inputs need to be sent again. Unlike the other embedding calls,
+    /// empty `text` is not an error, as there is no input to hold one; it
+    /// gives an empty result without sending anything.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_text_batched_partial(
+        &self,
+        name: impl Into<String>,
+        text: impl IntoIterator<Item = impl Into<String>>,
+        batch_size: usize,
+        concurrency: usize,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Vec<Result<Vec<f32>, IngrainError>> {
+        let name = name.into();
+        let text = into_strings(text);
+        let chunks: Vec<&[String]> = text.chunks(batch_size.max(1)).collect();
+
+        let responses: Vec<Result<TextEmbeddingResponse, IngrainError>> = stream::iter(&chunks)
+            .map(|chunk| self.embed_text(name.clone(), chunk.to_vec(), normalize, n_dims))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut results = Vec::with_capacity(text.len());
+        for (chunk, response) in chunks.iter().zip(responses) {
+            let response = response.and_then(|response| {
+                if response.embeddings.len() == chunk.len() {
+                    Ok(response)
+                } else {
+                    Err(IngrainError::UnexpectedResultCount {
+                        expected: chunk.len(),
+                        found: response.embeddings.len(),
+                    })
+                }
+            });
+            match response {
+                Ok(response) => results.extend(response.embeddings.into_iter().map(Ok)),
+                Err(e) => {
+                    let first = results.len();
+                    results.push(Err(e));
+                    results
+                        .extend((1..chunk.len()).map(|_| Err(IngrainError::ChunkFailed { first })));
+                }
+            }
+        }
+        results
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        let payload = ImageEmbeddingRequest {
+            image: non_empty(into_strings(image), "image")?,
+            normalize,
+            n_dims,
+            name: name.into(),
+            image_download_headers,
+        };
+        self.send_embed_image(&payload, &RequestOptions::default())
+            .await
+    }
+
+    async fn send_embed_image(
+        &self,
+        payload: &ImageEmbeddingRequest,
+        options: &RequestOptions<'_>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        self.auto_loading(&payload.name, || {
+            self.send_json(
+                Method::POST,
+                "embed_image",
+                ServerTarget::Inference,
+                Some(payload),
+                None::<&()>,
+                options,
+            )
+        })
+        .await
+    }
+
+    /// Like [`IngrainClient::embed_image`], also returning how many attempts
+    /// the call took and the errors of the attempts that were retried.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image_with_report(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> (Result<ImageEmbeddingResponse, IngrainError>, RetryReport) {
+        let report = Mutex::new(RetryReport::default());
+        let result = match non_empty(into_strings(image), "image") {
+            Ok(image) => {
+                let payload = ImageEmbeddingRequest {
+                    image,
+                    normalize,
+                    n_dims,
+                    name: name.into(),
+                    image_download_headers,
+                };
+                let options = RequestOptions {
+                    report: Some(&report),
+                    ..RequestOptions::default()
+                };
+                self.send_embed_image(&payload, &options).await
+            }
+            Err(e) => Err(e),
+        };
+        (result, report.into_inner().unwrap())
+    }
+
+    /// Like [`IngrainClient::embed_image`], also measuring the wall-clock
+    /// time of the whole call on the client, retries included.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image_timed(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Timed<ImageEmbeddingResponse>, IngrainError> {
+        timed(self.embed_image(name, image, normalize, n_dims, image_download_headers)).await
+    }
+
+    /// Like [`IngrainClient::embed_image`], taking inputs already checked
+    /// with [`ImageInput::parse`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image_inputs(
+        &self,
+        name: impl Into<String>,
+        images: Vec<ImageInput>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        self.embed_image(name, images, normalize, n_dims, image_download_headers)
+            .await
+    }
+
+    /// Embeds a single image and returns its embedding.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image_one(
+        &self,
+        name: impl Into<String>,
+        image: impl Into<String>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Vec<f32>, IngrainError> {
+        let response = self
+            .embed_image(
+                name,
+                [image.into()],
+                normalize,
+                n_dims,
+                image_download_headers,
+            )
+            .await?;
+        single(response.embeddings)
+    }
+
+    /// Reads local image files and embeds them as `data:` URIs. The MIME type
+    /// is taken from the file signature, falling back to the extension.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image_files(
+        &self,
+        name: impl Into<String>,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        let mut images = Vec::new();
+        for path in paths {
+            let path = path.as_ref();
+            let bytes = tokio::fs::read(path)
+                .await
+                .map_err(|source| IngrainError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+            let mime = image::sniff_mime(&bytes)
+                .or_else(|| image::mime_from_extension(path))
+                .ok_or_else(|| IngrainError::UnknownImageFormat {
+                    path: Some(path.to_path_buf()),
+                })?;
+            images.push(image::encode_data_uri(mime, &bytes));
+        }
+
+        self.embed_image(name, images, normalize, n_dims, None)
+            .await
+    }
+
+    /// Embeds in-memory images, sending each buffer as a `data:` URI of the
+    /// given type.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_image_bytes(
+        &self,
+        name: impl Into<String>,
+        images: Vec<(Vec<u8>, ImageMime)>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+    ) -> Result<ImageEmbeddingResponse, IngrainError> {
+        let images: Vec<String> = images
+            .iter()
+            .map(|(bytes, mime)| image::encode_data_uri(mime.as_str(), bytes))
+            .collect();
+
+        self.embed_image(name, images, normalize, n_dims, None)
+            .await
+    }
+
+    /// Embeds `images` in chunks of `batch_size`, running up to `concurrency`
+    /// requests at once, and yields an `(index, result)` pair for every input
+    /// as its chunk completes, so chunks may arrive out of input order. The
+    /// remaining chunks still run when one fails: its first input holds the
+    /// chunk's error and the others [`IngrainError::ChunkFailed`] pointing at
+    /// it. A `batch_size` or `concurrency` of zero is treated as one.
+    ///
+    /// Items are `(index, Result)` rather than `Result<(index, embedding)>`
+    /// because an error must say which input it belongs to, or a caller
+    /// writing results as they arrive could not tell which inputs were lost.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn embed_image_stream<N: Into<String>>(
+        &self,
+        name: N,
+        images: Vec<String>,
+        batch_size: usize,
+        concurrency: usize,
+    ) -> impl futures::Stream<Item = (usize, Result<Vec<f32>, IngrainError>)> + use<N> {
+        let client = self.clone();
+        let name = name.into();
+        let batch_size = batch_size.max(1);
+        let chunks: Vec<(usize, Vec<String>)> = images
+            .chunks(batch_size)
+            .enumerate()
+            .map(|(k, chunk)| (k * batch_size, chunk.to_vec()))
+            .collect();
+
+        stream::iter(chunks)
+            .map(move |(first, chunk)| {
+                let client = client.clone();
+                let name = name.clone();
+                async move {
+                    let expected = chunk.len();
+                    let response = client
+                        .embed_image(name, chunk, None, None, None)
+                        .await
+                        .and_then(|response| {
+                            if response.embeddings.len() == expected {
+                                Ok(response.embeddings)
+                            } else {
+                                Err(IngrainError::UnexpectedResultCount {
+                                    expected,
+                                    found: response.embeddings.len(),
+                                })
+                            }
+                        });
+                    (first, expected, response)
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .flat_map(|(first, expected, result)| {
+                let items: Vec<_> = match result {
+                    Ok(embeddings) => embeddings
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, embedding)| (first + i, Ok(embedding)))
+                        .collect(),
+                    Err(e) => std::iter::once((first, Err(e)))
+                        .chain(
+                            (first + 1..first + expected)
+                                .map(|index| (index, Err(IngrainError::ChunkFailed { first }))),
+                        )
+                        .collect(),
+                };
+                stream::iter(items)
+            })
+    }
+
+    /// Embeds texts and images with one request. Fails without sending
+    /// anything with [`IngrainError::EmptyInput`] when neither `text` nor
+    /// `image` is given, or when either is an empty list, and with
+    /// [`IngrainError::InvalidRequest`] when `image_download_headers` are
+    /// given without `image`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed(
+        &self,
+        name: impl Into<String>,
+        text: Option<Vec<String>>,
+        image: Option<Vec<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<EmbeddingResponse, IngrainError> {
+        check_mixed_inputs(
+            text.as_deref(),
+            image.as_deref(),
+            image_download_headers.as_ref(),
+        )?;
+
+        let payload = EmbeddingRequest {
+            image,
+            text,
+            normalize,
+            n_dims,
+            name: name.into(),
+            image_download_headers,
+        };
+
+        self.auto_loading(&payload.name, || {
+            self.post_json("embed", ServerTarget::Inference, &payload)
+        })
+        .await
+    }
+
+    /// Like [`IngrainClient::embed`], but splits `text` and `image` into
+    /// chunks of `batch_size` each and runs up to `concurrency` requests at
+    /// once. Each request carries the next chunk of both lists, so when one
+    /// list is longer the last requests carry only that kind of input.
+    /// Embeddings are returned in input order and the processing times of
+    /// all requests are summed. A `batch_size` or `concurrency` of zero is
+    /// treated as one.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_batched(
+        &self,
+        name: impl Into<String>,
+        text: Option<Vec<String>>,
+        image: Option<Vec<String>>,
+        batch_size: usize,
+        concurrency: usize,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<EmbeddingResponse, IngrainError> {
+        check_mixed_inputs(
+            text.as_deref(),
+            image.as_deref(),
+            image_download_headers.as_ref(),
+        )?;
+
+        let name = name.into();
+        let batch_size = batch_size.max(1);
+        let text_chunks: Vec<&[String]> = text
+            .as_deref()
+            .map(|text| text.chunks(batch_size).collect())
+            .unwrap_or_default();
+        let image_chunks: Vec<&[String]> = image
+            .as_deref()
+            .map(|image| image.chunks(batch_size).collect())
+            .unwrap_or_default();
+        let requests = text_chunks.len().max(image_chunks.len());
+
+        let responses: Vec<EmbeddingResponse> = stream::iter(0..requests)
+            .map(|k| {
+                self.embed(
+                    name.clone(),
+                    text_chunks.get(k).map(|chunk| chunk.to_vec()),
+                    image_chunks.get(k).map(|chunk| chunk.to_vec()),
+                    normalize,
+                    n_dims,
+                    // Requests past the last image chunk carry only text.
+                    image_download_headers
+                        .clone()
+                        .filter(|_| k < image_chunks.len()),
+                )
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let mut combined = EmbeddingResponse {
+            text_embeddings: text.as_ref().map(|text| Vec::with_capacity(text.len())),
+            image_embeddings: image.as_ref().map(|image| Vec::with_capacity(image.len())),
+            processing_time_ms: 0.0,
+        };
+        for (k, response) in responses.into_iter().enumerate() {
+            extend_chunk(
+                &mut combined.text_embeddings,
+                text_chunks.get(k).copied(),
+                response.text_embeddings,
+            )?;
+            extend_chunk(
+                &mut combined.image_embeddings,
+                image_chunks.get(k).copied(),
+                response.image_embeddings,
+            )?;
+            combined.processing_time_ms += response.processing_time_ms;
+        }
+        Ok(combined)
+    }
+
+    /// Like [`IngrainClient::embed`], but sends `text` to `embed_text` and
+    /// `image` to `embed_image` concurrently instead of one request to
+    /// `embed`, and sums their processing times. This only gives the same
+    /// embeddings when the model embeds each modality independently; prefer
+    /// `embed` for models or server versions that treat the combined
+    /// endpoint differently.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embed_split(
+        &self,
+        name: impl Into<String>,
+        text: Option<Vec<String>>,
+        image: Option<Vec<String>>,
+        normalize: Option<bool>,
+        n_dims: Option<Dims>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<EmbeddingResponse, IngrainError> {
+        check_mixed_inputs(
+            text.as_deref(),
+            image.as_deref(),
+            image_download_headers.as_ref(),
+        )?;
+
+        let name = name.into();
+        let (text_response, image_response) = tokio::try_join!(
+            async {
+                match text {
+                    Some(text) => self
+                        .embed_text(name.clone(), text, normalize, n_dims)
+                        .await
+                        .map(Some),
+                    None => Ok(None),
+                }
+            },
+            async {
+                match image {
+                    Some(image) => self
+                        .embed_image(
+                            name.clone(),
+                            image,
+                            normalize,
+                            n_dims,
+                            image_download_headers,
+                        )
+                        .await
+                        .map(Some),
+                    None => Ok(None),
+                }
+            },
+        )?;
+
+        Ok(EmbeddingResponse {
+            processing_time_ms: text_response
+                .as_ref()
+                .map_or(0.0, |response| response.processing_time_ms)
+                + image_response
+                    .as_ref()
+                    .map_or(0.0, |response| response.processing_time_ms),
+            text_embeddings: text_response.map(|response| response.embeddings),
+            image_embeddings: image_response.map(|response| response.embeddings),
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn classify_image(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageClassificationResponse, IngrainError> {
+        let payload = ImageClassificationRequest {
+            image: non_empty(into_strings(image), "image")?,
+            name: name.into(),
+            image_download_headers,
+        };
+
+        self.auto_loading(&payload.name, || {
+            self.post_json("classify_image", ServerTarget::Inference, &payload)
+        })
+        .await
+    }
+
+    /// Like [`IngrainClient::classify_image`], also measuring the wall-clock
+    /// time of the whole call on the client, retries included.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn classify_image_timed(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Timed<ImageClassificationResponse>, IngrainError> {
+        timed(self.classify_image(name, image, image_download_headers)).await
+    }
+
+    /// Classifies `images` in chunks of `batch_size`, running up to
+    /// `concurrency` requests at once. Probabilities are returned in input
+    /// order and the processing times of all chunks are summed. A
+    /// `batch_size` or `concurrency` of zero is treated as one. Fails with
+    /// [`IngrainError::EmptyInput`] when `images` is empty.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn classify_image_batched(
+        &self,
+        name: impl Into<String>,
+        images: Vec<String>,
+        batch_size: usize,
+        concurrency: usize,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<ImageClassificationResponse, IngrainError> {
+        let name = name.into();
+        let images = non_empty(images, "image")?;
+        let chunks: Vec<&[String]> = images.chunks(batch_size.max(1)).collect();
+
+        let responses: Vec<ImageClassificationResponse> = stream::iter(&chunks)
+            .map(|chunk| {
+                self.classify_image(name.clone(), chunk.to_vec(), image_download_headers.clone())
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let mut combined = ImageClassificationResponse {
+            probabilities: Vec::with_capacity(images.len()),
+            processing_time_ms: 0.0,
+        };
+        for (chunk, response) in chunks.iter().zip(responses) {
+            if response.probabilities.len() != chunk.len() {
+                return Err(IngrainError::UnexpectedResultCount {
+                    expected: chunk.len(),
+                    found: response.probabilities.len(),
+                });
+            }
+            combined.probabilities.extend(response.probabilities);
+            combined.processing_time_ms += response.processing_time_ms;
+        }
+        Ok(combined)
+    }
+
+    /// Classifies the images and pairs every probability with its label.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn classify_image_labeled(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Vec<Vec<(String, f32)>>, IngrainError> {
+        let name = name.into();
+        let labels = self.model_classification_labels(name.clone()).await?.labels;
+        let response = self
+            .classify_image(name, image, image_download_headers)
+            .await?;
+
+        response
+            .probabilities
+            .into_iter()
+            .map(|probabilities| {
+                if probabilities.len() != labels.len() {
+                    return Err(IngrainError::LabelMismatch {
+                        labels: labels.len(),
+                        probabilities: probabilities.len(),
+                    });
+                }
+                Ok(labels.iter().cloned().zip(probabilities).collect())
+            })
+            .collect()
+    }
+
+    /// Like [`IngrainClient::classify_image_labeled`], keeping only the `k`
+    /// most probable labels per image, highest first. A `k` larger than the
+    /// number of classes returns every class.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn classify_image_top_k(
+        &self,
+        name: impl Into<String>,
+        image: impl IntoIterator<Item = impl Into<String>>,
+        k: usize,
+        image_download_headers: Option<HashMap<String, String>>,
+    ) -> Result<Vec<Vec<(String, f32)>>, IngrainError> {
+        let mut results = self
+            .classify_image_labeled(name, image, image_download_headers)
+            .await?;
+
+        for result in &mut results {
+            result.sort_by(|a, b| b.1.total_cmp(&a.1));
+            result.truncate(k);
+        }
+        Ok(results)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_classification_labels(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<ModelClassificationLabelsResponse, IngrainError> {
+        let payload = ModelMetadataRequest { name: name.into() };
+        let cache = self.metadata_cache.as_deref();
+        if let Some(labels) = cache.and_then(|cache| cache.labels(&payload.name)) {
+            return Ok(ModelClassificationLabelsResponse { labels });
+        }
+
+        let response: ModelClassificationLabelsResponse = self
+            .request_json(
+                Method::GET,
+                "model_classification_labels",
+                ServerTarget::Model,
+                None::<&()>,
+                Some(&payload),
+            )
+            .await?;
+        if let Some(cache) = cache {
+            cache.set_labels(&payload.name, &response.labels);
+        }
+        Ok(response)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_embedding_size(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<ModelEmbeddingDimsResponse, IngrainError> {
+        let payload = ModelMetadataRequest { name: name.into() };
+        let cache = self.metadata_cache.as_deref();
+        if let Some(embedding_size) = cache.and_then(|cache| cache.embedding_size(&payload.name)) {
+            return Ok(ModelEmbeddingDimsResponse { embedding_size });
+        }
+
+        let response: ModelEmbeddingDimsResponse = self
+            .request_json(
+                Method::GET,
+                "model_embedding_size",
+                ServerTarget::Model,
+                None::<&()>,
+                Some(&payload),
+            )
+            .await?;
+        if let Some(cache) = cache {
+            cache.set_embedding_size(&payload.name, response.embedding_size);
+        }
+        Ok(response)
+    }
+
+    /// Fetches the embedding size and classification labels concurrently. An
+    /// endpoint answering 404 leaves its field `None`, since embedding models
+    /// have no labels and classifiers may have no embedding size; when both do,
+    /// the call fails with [`IngrainError::ModelNotFound`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn model_metadata(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<ModelMetadata, IngrainError> {
+        let name = name.into();
+        let (embedding_size, labels) = tokio::join!(
+            self.model_embedding_size(name.as_str()),
+            self.model_classification_labels(name.as_str())
+        );
+        let embedding_size = not_found_as_none(embedding_size)?.map(|r| r.embedding_size);
+        let labels = not_found_as_none(labels)?.map(|r| r.labels);
+        if embedding_size.is_none() && labels.is_none() {
+            return Err(IngrainError::ModelNotFound { name });
+        }
+        Ok(ModelMetadata {
+            embedding_size,
+            labels,
+        })
+    }
+}
+
+fn not_found_as_none<T>(result: Result<T, IngrainError>) -> Result<Option<T>, IngrainError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(IngrainError::Http { status, .. }) if status == StatusCode::NOT_FOUND => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::Method::{GET, POST};
+    use httpmock::{HttpMockResponse, MockServer};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_model_server_health_success() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "Model server healthy"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let response = client.model_server_health().await.unwrap();
+
+        assert_eq!(response.message, "Model server healthy");
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_http_error_with_structured_body() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/load_model");
+            then.status(404)
+                .header("Content-Type", "application/json")
+                .body(r#"{"detail": "library not supported"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let err = client
+            .load_model("missing-model", ModelLibrary::OpenClip)
+            .await
+            .unwrap_err();
+
+        match &err {
+            IngrainError::Http { status, detail, .. } => {
+                assert_eq!(*status, reqwest::StatusCode::NOT_FOUND);
+                assert_eq!(detail.as_deref(), Some("library not supported"));
+            }
+            other => panic!("expected Http error, got {:?}", other),
+        }
+        assert!(err.to_string().ends_with("library not supported"));
+    }
+
+    #[tokio::test]
+    async fn test_http_error_with_plain_text_body() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/load_model");
+            then.status(502).body("Bad Gateway");
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let err = client
+            .load_model("test-model", ModelLibrary::OpenClip)
+            .await
+            .unwrap_err();
+
+        match &err {
+            IngrainError::Http { body, detail, .. } => {
+                assert_eq!(body, "Bad Gateway");
+                assert!(detail.is_none());
+            }
+            other => panic!("expected Http error, got {:?}", other),
+        }
+        assert!(err.to_string().contains("(body: Bad Gateway)"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_for_returns_matching_model() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/metrics");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(
+                    r#"{"modelStats": [
+                        {"name": "model-a", "version": "1", "inferenceStats": {}, "inferenceCount": "3"},
+                        {"name": "model-b", "version": "2", "inferenceStats": {}, "inferenceCount": "7"}
+                    ]}"#,
+                );
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let stats = client.metrics_for("model-b").await.unwrap().unwrap();
+        assert_eq!(stats.version, "2");
+        assert_eq!(stats.inference_count, Some(7));
+
+        assert!(client.metrics_for("model-c").await.unwrap().is_none());
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_model_server_info() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/info");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"version": "0.4.2", "backend": "triton"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let info = client.model_server_info().await.unwrap();
+
+        assert_eq!(info.version, "0.4.2");
+        assert_eq!(info.extra["backend"], "triton");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_model_server_info_unsupported() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/info");
+            then.status(404)
+                .body(r#"{"detail": "No route for this path"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        assert!(matches!(
+            client.model_server_info().await,
+            Err(IngrainError::Unsupported { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ready_and_live_probes() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+
+        model_server.mock(|when, then| {
+            when.method(GET).path("/ready");
+            then.status(200).body("OK");
+        });
+        model_server.mock(|when, then| {
+            when.method(GET).path("/live");
+            then.status(200).body("OK");
+        });
+        inference_server.mock(|when, then| {
+            when.method(GET).path("/ready");
+            then.status(503).body("loading models");
+        });
+        inference_server.mock(|when, then| {
+            when.method(GET).path("/live");
+            then.status(200);
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        assert!(client.model_server_ready().await.unwrap());
+        assert!(client.model_server_live().await.unwrap());
+        assert!(!client.inference_server_ready().await.unwrap());
+        assert!(client.inference_server_live().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_inference_probes_check_every_host() {
+        let replica_a = MockServer::start();
+        let replica_b = MockServer::start();
+
+        let ready_a = replica_a.mock(|when, then| {
+            when.method(GET).path("/ready");
+            then.status(200);
+        });
+        let ready_b = replica_b.mock(|when, then| {
+            when.method(GET).path("/ready");
+            then.status(503).body("loading models");
+        });
+        replica_a.mock(|when, then| {
+            when.method(GET).path("/live");
+            then.status(200);
+        });
+        replica_b.mock(|when, then| {
+            when.method(GET).path("/live");
+            then.status(200);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_urls(&[&replica_a.url(""), &replica_b.url("")])
+            .build()
+            .unwrap();
+
+        assert!(!client.inference_server_ready().await.unwrap());
+        assert!(client.inference_server_live().await.unwrap());
+        ready_a.assert();
+        ready_b.assert();
+    }
+
+    #[tokio::test]
+    async fn test_load_model_inferred() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/load_model")
+                .body_includes(r#""library":"timm""#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "loaded"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        client
+            .load_model_inferred("hf_hub:timm/mobilenetv4_conv_medium.e250_r384_in12k_ft_in1k")
+            .await
+            .unwrap();
+        mock.assert();
+
+        assert!(matches!(
+            client.load_model_inferred("ViT-B-32").await,
+            Err(IngrainError::CannotInferLibrary { name }) if name == "ViT-B-32"
+        ));
+        mock.assert_calls(1);
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn test_metrics_stream_keeps_yielding_after_errors() {
+        let server = MockServer::start();
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let polls_clone = polls.clone();
+        let mock = server.mock(move |when, then| {
+            when.method(GET).path("/metrics");
+            then.respond_with(move |_req: &httpmock::HttpMockRequest| {
+                if polls_clone.fetch_add(1, Ordering::SeqCst) == 0 {
+                    HttpMockResponse::builder().status(503).body("busy").build()
+                } else {
+                    HttpMockResponse::builder()
+                        .status(200)
+                        .header("Content-Type", "application/json")
+                        .body(r#"{"modelStats": []}"#)
+                        .build()
+                }
+            });
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let items: Vec<_> = client
+            .metrics_stream(Duration::from_millis(10))
+            .take(2)
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[0], Err(IngrainError::Http { .. })));
+        assert!(items[1].as_ref().unwrap().model_stats.is_empty());
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_empty_inputs_fail_before_sending() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.any_request();
+            then.status(200);
+        });
+
+        let client = IngrainClient::new(&server.url(""), &server.url(""));
+        let no_inputs: Vec<String> = Vec::new();
+
+        assert!(matches!(
+            client
+                .embed_text("test-model", no_inputs.clone(), None, None)
+                .await,
+            Err(IngrainError::EmptyInput { input: "text" })
+        ));
+        assert!(matches!(
+            client
+                .embed_image("test-model", no_inputs.clone(), None, None, None)
+                .await,
+            Err(IngrainError::EmptyInput { input: "image" })
+        ));
+        assert!(matches!(
+            client
+                .classify_image("test-model", no_inputs.clone(), None)
+                .await,
+            Err(IngrainError::EmptyInput { input: "image" })
+        ));
+        assert!(matches!(
+            client
+                .embed(
+                    "test-model",
+                    Some(vec!["hello".to_string()]),
+                    Some(no_inputs),
+                    None,
+                    None,
+                    None
+                )
+                .await,
+            Err(IngrainError::EmptyInput { input: "image" })
+        ));
+        mock.assert_calls(0);
+    }
+
+    #[tokio::test]
+    async fn test_batched_calls_reject_empty_input() {
+        let client = IngrainClient::new("http://localhost:8687", "http://localhost:8686");
+        let no_inputs: Vec<String> = Vec::new();
+
+        assert!(matches!(
+            client
+                .embed_text_batched("test-model", no_inputs.clone(), 2, 2, None, None)
+                .await,
+            Err(IngrainError::EmptyInput { input: "text" })
+        ));
+        assert!(matches!(
+            client
+                .classify_image_batched("test-model", no_inputs.clone(), 2, 2, None)
+                .await,
+            Err(IngrainError::EmptyInput { input: "image" })
+        ));
+        assert!(
+            client
+                .embed_text_batched_partial("test-model", no_inputs, 2, 2, None, None)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embed_rejects_image_headers_without_images() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed")
+                .body_includes(r#""imageDownloadHeaders":{"Authorization":"Bearer x"}"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"textEmbeddings": [[0.1]], "imageEmbeddings": [[0.2]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let headers = HashMap::from([("Authorization".to_string(), "Bearer x".to_string())]);
+
+        let result = client
+            .embed(
+                "test-model",
+                Some(vec!["hello".to_string()]),
+                None,
+                None,
+                None,
+                Some(headers.clone()),
+            )
+            .await;
+        assert!(matches!(result, Err(IngrainError::InvalidRequest { .. })));
+        mock.assert_calls(0);
+
+        let response = client
+            .embed(
+                "test-model",
+                Some(vec!["hello".to_string()]),
+                Some(vec!["https://example.com/a.png".to_string()]),
+                None,
+                None,
+                Some(headers),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.image_embeddings, Some(vec![vec![0.2]]));
+        mock.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_wire_format_selects_field_casing() {
+        let server = MockServer::start();
+
+        let camel = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed")
+                .json_body(serde_json::json!({
+                    "name": "test-model",
+                    "text": null,
+                    "image": ["https://example.com/a.png"],
+                    "normalize": null,
+                    "nDims": 64,
+                    "imageDownloadHeaders": {"X-Token": "t"}
+                }));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"imageEmbeddings": [[0.2]], "processingTimeMs": 1.0}"#);
+        });
+        let snake = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed")
+                .json_body(serde_json::json!({
+                    "name": "test-model",
+                    "text": null,
+                    "image": ["https://example.com/a.png"],
+                    "normalize": null,
+                    "n_dims": 64,
+                    "image_download_headers": {"X-Token": "t"}
+                }));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"imageEmbeddings": [[0.2]], "processingTimeMs": 1.0}"#);
+        });
+
+        for wire_format in [WireFormat::CamelCase, WireFormat::SnakeCase] {
+            let client = IngrainClient::builder()
+                .inference_server_url(&server.url(""))
+                .wire_format(wire_format)
+                .build()
+                .unwrap();
+            client
+                .embed(
+                    "test-model",
+                    None,
+                    Some(vec!["https://example.com/a.png".to_string()]),
+                    None,
+                    Dims::new(64),
+                    Some(HashMap::from([("X-Token".to_string(), "t".to_string())])),
+                )
+                .await
+                .unwrap();
+        }
+
+        camel.assert_calls(1);
+        snake.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_embed_without_inputs_fails_before_sending() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.any_request();
+            then.status(200);
+        });
+
+        let client = IngrainClient::new(&server.url(""), &server.url(""));
+
+        let result = client
+            .embed("test-model", None, None, None, None, None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(IngrainError::EmptyInput {
+                input: "text or image"
+            })
+        ));
+        let result = client
+            .embed_batched("test-model", None, None, 2, 2, None, None, None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(IngrainError::EmptyInput {
+                input: "text or image"
+            })
+        ));
+        mock.assert_calls(0);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_one() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["hello"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2, 0.3]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let embedding = client
+            .embed_text_one("test-model", "hello", None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(embedding, vec![0.1, 0.2, 0.3]);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_one() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_image")
+                .body_includes(r#""image":["https://example.com/cat.png"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.4, 0.5]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let embedding = client
+            .embed_image_one(
+                "test-model",
+                "https://example.com/cat.png",
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(embedding, vec![0.4, 0.5]);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_one_rejects_extra_embeddings() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1], [0.2]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        assert!(matches!(
+            client
+                .embed_text_one("test-model", "hello", None, None)
+                .await,
+            Err(IngrainError::UnexpectedResultCount {
+                expected: 1,
+                found: 2
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_model_embedding_size_is_cached() {
+        let server = MockServer::start();
+
+        let size_mock = server.mock(|when, then| {
+            when.method(GET).path("/model_embedding_size");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddingSize": 384}"#);
+        });
+        let unload_mock = server.mock(|when, then| {
+            when.method(POST).path("/unload_model");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "unloaded"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .cache_model_metadata(true)
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let response = client.model_embedding_size("test-model").await.unwrap();
+            assert_eq!(response.embedding_size, 384);
+        }
+        size_mock.assert_calls(1);
+
+        client.unload_model("test-model").await.unwrap();
+        client.model_embedding_size("test-model").await.unwrap();
+        size_mock.assert_calls(2);
+
+        client.clear_cache();
+        client.model_embedding_size("test-model").await.unwrap();
+        size_mock.assert_calls(3);
+        unload_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_auto_load_loads_and_retries_once() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+        let embeds = Arc::new(AtomicUsize::new(0));
+
+        let embeds_clone = embeds.clone();
+        let embed_mock = inference_server.mock(move |when, then| {
+            when.method(POST).path("/embed_text");
+            then.respond_with(move |_req: &httpmock::HttpMockRequest| {
+                if embeds_clone.fetch_add(1, Ordering::SeqCst) == 0 {
+                    HttpMockResponse::builder()
+                        .status(404)
+                        .body(r#"{"detail": "Model test-model is not loaded"}"#)
+                        .build()
+                } else {
+                    HttpMockResponse::builder()
+                        .status(200)
+                        .header("Content-Type", "application/json")
+                        .body(r#"{"embeddings": [[0.1, 0.2]], "processingTimeMs": 1.0}"#)
+                        .build()
+                }
+            });
+        });
+        let load_mock = model_server.mock(|when, then| {
+            when.method(POST)
+                .path("/load_model")
+                .body_includes(r#""library":"sentence_transformers""#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "loaded"}"#);
+        });
+        let repository_mock = model_server.mock(|when, then| {
+            when.method(GET).path("/repository_models");
+            then.respond_with(|_req: &httpmock::HttpMockRequest| repository_response("READY"));
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        let response = client
+            .with_auto_load(ModelLibrary::SentenceTransformers)
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2]]);
+        embed_mock.assert_calls(2);
+        load_mock.assert();
+        repository_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_auto_load_ignores_404_without_not_loaded_detail() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+
+        let embed_mock = inference_server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(404)
+                .body(r#"{"detail": "No route for this path"}"#);
+        });
+        let load_mock = model_server.mock(|when, then| {
+            when.method(POST).path("/load_model");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "loaded"}"#);
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        let err = client
+            .with_auto_load(ModelLibrary::SentenceTransformers)
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, IngrainError::Http { status, .. } if status == StatusCode::NOT_FOUND)
+        );
+        embed_mock.assert();
+        load_mock.assert_calls(0);
+    }
+
+    #[tokio::test]
+    async fn test_model_errors_distinguish_not_found_from_not_loaded() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+
+        inference_server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(404)
+                .body(r#"{"detail": "Model test-model is not loaded"}"#);
+        });
+        model_server.mock(|when, then| {
+            when.method(POST).path("/load_model");
+            then.status(404)
+                .body(r#"{"detail": "Model missing-model not found in repository"}"#);
+        });
+        model_server.mock(|when, then| {
+            when.method(POST).path("/unload_model");
+            then.status(409)
+                .body(r#"{"detail": "Unload already in progress"}"#);
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        let result = client.embed_text("test-model", ["hello"], None, None).await;
+        assert!(matches!(
+            result,
+            Err(IngrainError::ModelNotLoaded { name }) if name == "test-model"
+        ));
+
+        let result = client
+            .load_model("missing-model", ModelLibrary::SentenceTransformers)
+            .await;
+        assert!(matches!(
+            result,
+            Err(IngrainError::ModelNotFound { name }) if name == "missing-model"
+        ));
+
+        let result = client.unload_model("test-model").await;
+        assert!(matches!(
+            result,
+            Err(IngrainError::Http { status, .. }) if status == StatusCode::CONFLICT
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_raw_keeps_unmodeled_fields() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(
+                    r#"{"embeddings": [[0.5]], "processingTimeMs": 1.0, "tokenCounts": [3], "modelVersion": "2"}"#,
+                );
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let response = client
+            .embed_text_raw("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "embeddings": [[0.5]],
+                "processingTimeMs": 1.0,
+                "tokenCounts": [3],
+                "modelVersion": "2"
+            })
+        );
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_fails_over_to_next_inference_server() {
+        let down = MockServer::start();
+        let up = MockServer::start();
+
+        let down_mock = down.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503).body("unavailable");
+        });
+        let up_mock = up.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_urls(&[&down.url(""), &up.url("")])
+            .retries(1)
+            .build()
+            .unwrap();
+
+        let response = client
+            .embed_text("test-model", vec!["hello".to_string()], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2]]);
+        down_mock.assert_calls(2);
+        up_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_spreads_calls_and_skips_down_hosts() {
+        let servers: Vec<MockServer> = (0..3).map(|_| MockServer::start()).collect();
+        let mocks: Vec<_> = servers
+            .iter()
+            .map(|server| {
+                server.mock(|when, then| {
+                    when.method(POST).path("/embed_text");
+                    then.status(200)
+                        .header("Content-Type", "application/json")
+                        .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+                })
+            })
+            .collect();
+        let urls: Vec<String> = servers.iter().map(|server| server.url("")).collect();
+        let url_refs: Vec<&str> = urls.iter().map(String::as_str).collect();
+
+        let client = IngrainClient::builder()
+            .inference_server_urls(&url_refs)
+            .load_balancing(LoadBalancing::RoundRobin)
+            .build()
+            .unwrap();
+
+        for _ in 0..30 {
+            client
+                .embed_text("test-model", vec!["hello".to_string()], None, None)
+                .await
+                .unwrap();
+        }
+        for mock in &mocks {
+            mock.assert_calls(10);
+        }
+
+        // With the middle host down its turns fall through to the last one,
+        // and the rotation carries on.
+        let down = MockServer::start();
+        let down_mock = down.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503);
+        });
+        let client = IngrainClient::builder()
+            .inference_server_urls(&[url_refs[0], &down.url(""), url_refs[2]])
+            .load_balancing(LoadBalancing::RoundRobin)
+            .build()
+            .unwrap();
+
+        for _ in 0..30 {
+            client
+                .embed_text("test-model", vec!["hello".to_string()], None, None)
+                .await
+                .unwrap();
+        }
+        mocks[0].assert_calls(20);
+        down_mock.assert_calls(10);
+        mocks[2].assert_calls(30);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast_once_open() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503).body("unavailable");
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .circuit_breaker(2, Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let result = client
+                .embed_text("test-model", vec!["hello".to_string()], None, None)
+                .await;
+            assert!(matches!(result, Err(IngrainError::Http { .. })));
+        }
+        for _ in 0..3 {
+            let result = client
+                .embed_text("test-model", vec!["hello".to_string()], None, None)
+                .await;
+            assert!(matches!(result, Err(IngrainError::CircuitOpen { .. })));
+        }
+
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_on_trial_call_keeps_breaker_tripped() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["fail"]"#);
+            then.status(503).body("unavailable");
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["slow"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#)
+                .delay(Duration::from_secs(2));
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .circuit_breaker(1, Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let result = client.embed_text("test-model", ["fail"], None, None).await;
+        assert!(matches!(result, Err(IngrainError::Http { .. })));
+
+        // Once the cooldown has passed, this call is the half-open trial.
+        sleep(Duration::from_millis(250)).await;
+        let result = client
+            .with_deadline(Instant::now() + Duration::from_millis(50))
+            .embed_text("test-model", ["slow"], None, None)
+            .await;
+        assert!(matches!(result, Err(IngrainError::DeadlineExceeded)));
+
+        // A closed breaker would let both calls through; a tripped one lets
+        // through a single trial.
+        let breakers = client.breakers.as_deref().unwrap();
+        let base_url = client.inference_server_url();
+        assert!(breakers.check(base_url).is_ok());
+        assert!(matches!(
+            breakers.check(base_url),
+            Err(IngrainError::CircuitOpen { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failover_does_not_retry_client_errors_elsewhere() {
+        let first = MockServer::start();
+        let second = MockServer::start();
+
+        let first_mock = first.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(400).body("bad request");
+        });
+        let second_mock = second.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_urls(&[&first.url(""), &second.url("")])
+            .build()
+            .unwrap();
+
+        let result = client
+            .embed_text("test-model", vec!["hello".to_string()], None, None)
+            .await;
+
+        assert!(
+            matches!(result, Err(IngrainError::Http { status, .. }) if status == StatusCode::BAD_REQUEST)
+        );
+        first_mock.assert();
+        second_mock.assert_calls(0);
+    }
+
+    #[cfg(feature = "embedding-cache")]
+    #[tokio::test]
+    async fn test_embedding_cache_only_requests_misses() {
+        let server = MockServer::start();
+
+        let first = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["hello"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2]], "processingTimeMs": 1.0}"#);
+        });
+        let second = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["world"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.3, 0.4]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .embedding_cache(16)
+            .build()
+            .unwrap();
+
+        let response = client
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2]]);
+
+        let response = client
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2]]);
+        assert_eq!(response.processing_time_ms, 0.0);
+
+        let response = client
+            .embed_text("test-model", ["world", "hello"], None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.embeddings, vec![vec![0.3, 0.4], vec![0.1, 0.2]]);
+
+        first.assert_calls(1);
+        second.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_model_sends_one_inference_request() {
+        let server = MockServer::start();
+
+        let embed = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+        let classify = server.mock(|when, then| {
+            when.method(POST)
+                .path("/classify_image")
+                .body_includes("data:image/png;base64,");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"probabilities": [[1.0]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let elapsed = client
+            .warmup_model("intfloat/e5-small-v2", ModelLibrary::SentenceTransformers)
+            .await
+            .unwrap();
+        assert!(elapsed > Duration::ZERO);
+        embed.assert_calls(1);
+        classify.assert_calls(0);
+
+        let elapsed = client
+            .warmup_model("timm/resnet18.a1_in1k", ModelLibrary::Timm)
+            .await
+            .unwrap();
+        assert!(elapsed > Duration::ZERO);
+        embed.assert_calls(1);
+        classify.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_batched_partial_keeps_successful_chunks() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["a","b"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[1.0], [2.0]], "processingTimeMs": 1.0}"#);
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["c","d"]"#);
+            then.status(500).body("boom");
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["e"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[5.0]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let results = client
+            .embed_text_batched_partial("test-model", ["a", "b", "c", "d", "e"], 2, 2, None, None)
+            .await;
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].as_ref().unwrap(), &vec![1.0]);
+        assert_eq!(results[1].as_ref().unwrap(), &vec![2.0]);
+        assert!(
+            matches!(results[2], Err(IngrainError::Http { status, .. }) if status == StatusCode::INTERNAL_SERVER_ERROR)
+        );
+        assert!(matches!(
+            results[3],
+            Err(IngrainError::ChunkFailed { first: 2 })
+        ));
+        assert_eq!(results[4].as_ref().unwrap(), &vec![5.0]);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_timed_includes_client_time() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .delay(Duration::from_millis(50))
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 20.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let timed = client
+            .embed_text_timed("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(timed.value.embeddings, vec![vec![0.1]]);
+        assert!(timed.client_elapsed >= timed.value.processing_time());
+        assert!(timed.client_elapsed >= Duration::from_millis(50));
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_response_compression_decodes_gzip_bodies() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"labels": ["cat", "dog"]}"#).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/model_classification_labels")
+                .header_exists("accept-encoding");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .header("Content-Encoding", "gzip")
+                .body(compressed);
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .response_compression(true)
+            .build()
+            .unwrap();
+
+        let response = client
+            .model_classification_labels("test-model")
+            .await
+            .unwrap();
+
+        assert_eq!(response.labels, vec!["cat", "dog"]);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_inputs() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_image").body_includes(
+                r#""image":["https://example.com/cat.png","data:image/gif;base64,R0lG"]"#,
+            );
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1], [0.2]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let images = vec![
+            ImageInput::parse("https://example.com/cat.png").unwrap(),
+            ImageInput::parse("data:image/gif;base64,R0lG").unwrap(),
+        ];
+        let response = client
+            .embed_image_inputs("test-model", images, None, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings.len(), 2);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_with_report_records_retried_attempts() {
+        let server = MockServer::start();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        server.mock(move |when, then| {
+            when.method(POST).path("/embed_text");
+            then.respond_with(move |_req: &httpmock::HttpMockRequest| {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    HttpMockResponse::builder().status(503).body("busy").build()
+                } else {
+                    HttpMockResponse::builder()
+                        .status(200)
+                        .header("Content-Type", "application/json")
+                        .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#)
+                        .build()
+                }
+            });
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .retries(3)
+            .build()
+            .unwrap();
+
+        let (result, report) = client
+            .embed_text_with_report("test-model", ["hello"], None, None)
+            .await;
+
+        assert_eq!(result.unwrap().embeddings, vec![vec![0.1]]);
+        assert_eq!(report.attempts, 3);
+        assert_eq!(report.errors.len(), 2);
+        assert!(report.errors.iter().all(|e| matches!(e, IngrainError::Http { status, .. } if *status == StatusCode::SERVICE_UNAVAILABLE)));
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_hook_fires_before_each_retry() {
+        let server = MockServer::start();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        server.mock(move |when, then| {
+            when.method(POST).path("/embed_text");
+            then.respond_with(move |_req: &httpmock::HttpMockRequest| {
+                if counter.fetch_add(1, Ordering::SeqCst) < 3 {
+                    HttpMockResponse::builder()
+                        .status(502)
+                        .body("bad gateway")
+                        .build()
+                } else {
+                    HttpMockResponse::builder()
+                        .status(200)
+                        .header("Content-Type", "application/json")
+                        .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#)
+                        .build()
+                }
+            });
+        });
+
+        let retried = Arc::new(Mutex::new(Vec::new()));
+        let seen = retried.clone();
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .retries(5)
+            .on_retry(move |attempt, err| {
+                assert!(matches!(err, IngrainError::Http { .. }));
+                seen.lock().unwrap().push(attempt);
+            })
+            .build()
+            .unwrap();
+
+        client
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(*retried.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_gzip_requests_above_threshold() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        fn gunzip(body: &[u8]) -> Option<String> {
+            let mut text = String::new();
+            GzDecoder::new(body).read_to_string(&mut text).ok()?;
+            Some(text)
+        }
+
+        let server = MockServer::start();
+        let long_text = "a".repeat(2000);
+
+        let expected = long_text.clone();
+        let compressed = server.mock(move |when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .header("content-encoding", "gzip")
+                .header("content-type", "application/json")
+                .is_true(move |req| {
+                    gunzip(req.body().as_ref()).is_some_and(|body| body.contains(&expected))
+                });
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+        let plain = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .header_missing("content-encoding")
+                .body_includes(r#""text":["short"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.2]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .gzip_requests_above(1024)
+            .build()
+            .unwrap();
+
+        client
+            .embed_text("test-model", [long_text], None, None)
+            .await
+            .unwrap();
+        client
+            .embed_text("test-model", ["short"], None, None)
+            .await
+            .unwrap();
+
+        compressed.assert();
+        plain.assert();
+    }
+
+    #[tokio::test]
+    async fn test_strict_responses_reject_unknown_fields() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0, "tokenCount": 3}"#);
+        });
+
+        let lenient = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let response = lenient
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.embeddings, vec![vec![0.1]]);
+
+        let strict = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .strict_responses(true)
+            .build()
+            .unwrap();
+        let result = strict.embed_text("test-model", ["hello"], None, None).await;
+        match result {
+            Err(IngrainError::UnexpectedField { path, .. }) => assert_eq!(path, "tokenCount"),
+            other => panic!("expected UnexpectedField, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_with_meta_returns_status_and_headers() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .header("x-request-id", "req-42");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .header("X-Request-Id", "req-42")
+                .header("ETag", "\"v1\"")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let (response, meta) = client
+            .with_request_id("req-42")
+            .embed_text_with_meta("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings, vec![vec![0.1]]);
+        assert_eq!(meta.status, StatusCode::OK);
+        assert_eq!(meta.request_id.as_deref(), Some("req-42"));
+        assert_eq!(meta.etag.as_deref(), Some("\"v1\""));
+    }
+
+    #[tokio::test]
+    async fn test_embed_batched_reassembles_unequal_inputs() {
+        let server = MockServer::start();
+
+        // Three texts and one image in chunks of two: the first request
+        // carries both kinds of input and the second only the last text.
+        let both = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed")
+                .body_includes(r#""text":["a","b"]"#)
+                .body_includes(r#""image":["i"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"textEmbeddings": [[1.0], [2.0]], "imageEmbeddings": [[9.0]], "processingTimeMs": 1.0}"#);
+        });
+        let text_only = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed")
+                .body_includes(r#""text":["c"]"#)
+                .body_includes(r#""image":null"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"textEmbeddings": [[3.0]], "imageEmbeddings": null, "processingTimeMs": 2.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let response = client
+            .embed_batched(
+                "test-model",
+                Some(vec!["a".into(), "b".into(), "c".into()]),
+                Some(vec!["i".into()]),
+                2,
+                2,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.text_embeddings,
+            Some(vec![vec![1.0], vec![2.0], vec![3.0]])
+        );
+        assert_eq!(response.image_embeddings, Some(vec![vec![9.0]]));
+        assert_eq!(response.processing_time_ms, 3.0);
+        both.assert_calls(1);
+        text_only.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_stream_yields_every_index_once() {
+        let server = MockServer::start();
+
+        for (images, embeddings) in [
+            (r#""image":["a","b"]"#, "[[0.0], [1.0]]"),
+            (r#""image":["c","d"]"#, "[[2.0], [3.0]]"),
+            (r#""image":["e"]"#, "[[4.0]]"),
+        ] {
+            server.mock(move |when, then| {
+                when.method(POST).path("/embed_image").body_includes(images);
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(format!(
+                        r#"{{"embeddings": {}, "processingTimeMs": 1.0}}"#,
+                        embeddings
+                    ));
+            });
+        }
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let images = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+
+        let mut items: Vec<(usize, Result<Vec<f32>, IngrainError>)> = client
+            .embed_image_stream("test-model", images, 2, 3)
+            .collect()
+            .await;
+        items.sort_by_key(|(index, _)| *index);
+
+        assert_eq!(items.len(), 5);
+        for (expected, (index, embedding)) in items.into_iter().enumerate() {
+            assert_eq!(index, expected);
+            assert_eq!(embedding.unwrap(), vec![expected as f32]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_stream_reports_every_index_of_failed_chunk() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_image")
+                .body_includes(r#""image":["a","b"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.0], [1.0]], "processingTimeMs": 1.0}"#);
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_image")
+                .body_includes(r#""image":["c","d"]"#);
+            then.status(400).body(r#"{"detail": "bad image"}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let images = ["a", "b", "c", "d"].map(String::from).to_vec();
+
+        let mut items: Vec<(usize, Result<Vec<f32>, IngrainError>)> = client
+            .embed_image_stream("test-model", images, 2, 2)
+            .collect()
+            .await;
+        items.sort_by_key(|(index, _)| *index);
+
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0].1.as_ref().unwrap(), &vec![0.0]);
+        assert_eq!(items[1].1.as_ref().unwrap(), &vec![1.0]);
+        assert!(matches!(items[2], (2, Err(IngrainError::Http { .. }))));
+        assert!(matches!(
+            items[3],
+            (3, Err(IngrainError::ChunkFailed { first: 2 }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_embed_split_calls_both_endpoints() {
+        let server = MockServer::start();
+
+        let text_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["hello"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2]], "processingTimeMs": 1.5}"#);
+        });
+        let image_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_image")
+                .body_includes(r#""image":["https://example.com/a.png"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.3, 0.4]], "processingTimeMs": 2.5}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let response = client
+            .embed_split(
+                "test-model",
+                Some(vec!["hello".to_string()]),
+                Some(vec!["https://example.com/a.png".to_string()]),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.text_embeddings, Some(vec![vec![0.1, 0.2]]));
+        assert_eq!(response.image_embeddings, Some(vec![vec![0.3, 0.4]]));
+        assert_eq!(response.processing_time_ms, 4.0);
+        text_mock.assert();
+        image_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_health_all_reports_each_server() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+
+        let model_mock = model_server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "Model server healthy"}"#);
+        });
+        let inference_mock = inference_server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(503).body("starting");
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        let (model, inference) = client.health_all().await;
+
+        assert_eq!(model.unwrap().message, "Model server healthy");
+        assert!(matches!(
+            inference,
+            Err(IngrainError::Http { status, .. }) if status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+        ));
+        model_mock.assert();
+        inference_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_model_server_health_failure() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(500)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "Internal Server Error"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client.model_server_health().await;
+
+        match result {
+            Err(IngrainError::Http { status, body, .. }) => {
+                assert_eq!(status, reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+                assert!(body.contains("Internal Server Error"));
+            }
+            other => panic!("expected Http error, got {:?}", other),
+        }
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_loaded_models_deserialize_error() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"unexpected": true}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client.loaded_models().await;
+
+        match result {
+            Err(IngrainError::Deserialize { body, .. }) => {
+                assert_eq!(body, r#"{"unexpected": true}"#);
+            }
+            other => panic!("expected Deserialize error, got {:?}", other),
+        }
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_error_names_json_path() {
+        let server = MockServer::start();
+
+        let batch =
+            r#"{"batchSize": 1, "computeInput": {}, "computeInfer": {}, "computeOutput": {}}"#;
+        let body = format!(
+            r#"{{"modelStats": [{{"name": "a", "version": "1", "inferenceStats": {{}}, "batchStats": [{}, {}]}}]}}"#,
+            batch,
+            batch.replace(r#""batchSize": 1"#, r#""batchSize": [1]"#)
+        );
+        server.mock(move |when, then| {
+            when.method(GET).path("/metrics");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(body);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let err = client.metrics().await.unwrap_err();
+
+        match &err {
+            IngrainError::Deserialize { path, .. } => {
+                assert_eq!(path, "modelStats[0].batchStats[1].batchSize")
+            }
+            other => panic!("expected Deserialize error, got {:?}", other),
+        }
+        assert!(
+            err.to_string()
+                .contains("at modelStats[0].batchStats[1].batchSize")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_fails_after_retries() {
+        let server = MockServer::start();
+
+        // Always fail
+        let fail_mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_image");
+            then.status(500).body("Internal Error");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 2, 10);
+
+        let result = client
+            .embed_image(
+                "test-model".to_string(),
+                vec!["image_url".to_string()],
+                None,
+                None,
+                None,
+            )
+            .await;
+
+        match result {
+            Err(IngrainError::RetriesExhausted { attempts, last }) => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*last, IngrainError::Http { .. }));
+            }
+            other => panic!("expected RetriesExhausted error, got {:?}", other),
+        }
+
+        fail_mock.assert_calls(3);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_client_error_is_not_retried() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(400).body("Bad Request");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 2, 10);
+
+        let result = client
+            .embed_text("test-model".to_string(), vec!["hi".to_string()], None, None)
+            .await;
+
+        match result {
+            Err(IngrainError::Http { status, .. }) => assert_eq!(status, StatusCode::BAD_REQUEST),
+            other => panic!("expected Http error, got {:?}", other),
+        }
+        mock.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_transient_error_uses_all_attempts() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503).body("Service Unavailable");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 2, 10);
+
+        let result = client
+            .embed_text("test-model".to_string(), vec!["hi".to_string()], None, None)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(IngrainError::RetriesExhausted { attempts: 3, .. })
+        ));
+        mock.assert_calls(3);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_custom_retry_on() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(400).body("Bad Request");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 2, 10)
+                .with_retry_on(|status| status.is_client_error());
+
+        let result = client
+            .embed_text("test-model".to_string(), vec!["hi".to_string()], None, None)
+            .await;
+
+        assert!(result.is_err());
+        mock.assert_calls(3);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_honors_retry_after() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503)
+                .header("Retry-After", "2")
+                .body("Service Unavailable");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 1, 10);
+
+        let start = std::time::Instant::now();
+        let result = client
+            .embed_text("test-model".to_string(), vec!["hi".to_string()], None, None)
+            .await;
+        let elapsed = start.elapsed();
+
+        assert!(result.is_err());
+        assert!(elapsed >= std::time::Duration::from_secs(2));
+        assert!(elapsed < std::time::Duration::from_secs(4));
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused_is_a_connect_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = IngrainClient::new("http://localhost:8687", &url);
+
+        let result = client
+            .embed_text("test-model", vec!["hi".to_string()], None, None)
+            .await;
+
+        assert!(matches!(result, Err(IngrainError::Connect(_))));
+    }
+
+    #[tokio::test]
+    async fn test_client_with_pool_options_sends_requests() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .pool_max_idle_per_host(8)
+            .pool_idle_timeout(Duration::from_secs(120))
+            .tcp_keepalive(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            client.inference_server_health().await.unwrap();
+        }
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_http_version_options_send_requests() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let http1 = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .http1_only()
+            .build()
+            .unwrap();
+        http1.inference_server_health().await.unwrap();
+
+        let http2 = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .http2_prior_knowledge(true)
+            .build()
+            .unwrap();
+        http2.inference_server_health().await.unwrap();
+
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = MockServer::start();
+
+        let _mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .delay(std::time::Duration::from_secs(3))
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .request_timeout(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let result = client
+            .embed_text("test-model".to_string(), vec!["hi".to_string()], None, None)
+            .await;
+
+        assert!(matches!(result, Err(IngrainError::Timeout(_))));
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_on_load_model() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/load_model")
+                .header("Authorization", "Bearer secret-token");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "Model loaded"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .auth(AuthConfig::Bearer("secret-token".to_string()))
+            .build()
+            .unwrap();
+
+        let result = client
+            .load_model("test-model", ModelLibrary::OpenClip)
+            .await;
+
+        assert!(result.is_ok());
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_api_key_auth_on_retried_embed_text() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .header("X-API-Key", "secret-key");
+            then.status(503).body("Service Unavailable");
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .retries(1)
+            .auth(AuthConfig::ApiKey {
+                header: "X-API-Key".to_string(),
+                value: "secret-key".to_string(),
+            })
+            .build()
+            .unwrap();
+
+        let result = client
+            .embed_text("test-model".to_string(), vec!["hi".to_string()], None, None)
+            .await;
+
+        assert!(result.is_err());
+        // Both the original and the cloned retry request carried the header.
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_default_headers_on_metrics_and_classify_image() {
+        let server = MockServer::start();
+
+        let metrics_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/metrics")
+                .header("X-Tenant-Id", "tenant-a");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"modelStats": []}"#);
+        });
+
+        let classify_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/classify_image")
+                .header("X-Tenant-Id", "tenant-a")
+                .header("X-Trace", "abc");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"probabilities": [[0.9, 0.1]], "processingTimeMs": 3.0}"#);
+        });
+
+        let mut headers = HashMap::new();
+        headers.insert("X-Trace".to_string(), "abc".to_string());
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .default_header("X-Tenant-Id", "tenant-a")
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        assert!(client.metrics().await.is_ok());
+        assert!(
+            client
+                .classify_image(
+                    "test-model".to_string(),
+                    vec!["image_url".to_string()],
+                    None,
+                )
+                .await
+                .is_ok()
+        );
+
+        metrics_mock.assert();
+        classify_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_default_and_override() {
+        let server = MockServer::start();
+
+        let default_mock = server.mock(|when, then| {
+            when.method(GET).path("/health").header(
+                "User-Agent",
+                concat!("ingrain_rs/", env!("CARGO_PKG_VERSION")),
+            );
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+        let custom_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/health")
+                .header("User-Agent", "my-pipeline/2.0");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), &server.url(""));
+        assert!(client.model_server_health().await.is_ok());
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .user_agent("my-pipeline/2.0")
+            .build()
+            .unwrap();
+        assert!(client.model_server_health().await.is_ok());
+
+        default_mock.assert();
+        custom_mock.assert();
+    }
+
+    #[test]
+    fn test_getters_reflect_constructor_arguments() {
+        let client =
+            IngrainClient::new_with_retries("http://model:8687/", "http://inference:8686", 3, 250);
+
+        assert_eq!(client.model_server_url(), "http://model:8687");
+        assert_eq!(client.inference_server_url(), "http://inference:8686");
+        assert_eq!(client.inference_server_urls(), ["http://inference:8686"]);
+        assert_eq!(client.retries(), 3);
+        assert_eq!(client.retry_delay_ms(), 250);
+    }
+
+    #[tokio::test]
+    async fn test_with_client_uses_injected_client() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/health")
+                .header("User-Agent", "custom-agent/1.0");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let http_client = Client::builder()
+            .user_agent("custom-agent/1.0")
+            .build()
+            .unwrap();
+        let client = IngrainClient::with_client(&server.url(""), &server.url(""), http_client);
+
+        assert!(client.model_server_health().await.is_ok());
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_cloned_client_issues_requests() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/health")
+                .header("Authorization", "Bearer secret-token");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .auth(AuthConfig::Bearer("secret-token".to_string()))
+            .build()
+            .unwrap();
+        let cloned = client.clone();
+
+        let handle = tokio::spawn(async move { cloned.inference_server_health().await });
+        assert!(handle.await.unwrap().is_ok());
+        mock.assert();
+
+        assert!(!format!("{:?}", client).contains("secret-token"));
+    }
+
+    #[tokio::test]
+    async fn test_is_model_loaded() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"models": [{"name": "test-model", "library": "timm"}]}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        assert!(client.is_model_loaded("test-model").await.unwrap());
+        assert!(!client.is_model_loaded("other-model").await.unwrap());
+
+        let model = client.loaded_model("test-model").await.unwrap().unwrap();
+        assert_eq!(model.library, ModelLibrary::Timm);
+        assert!(client.loaded_model("other-model").await.unwrap().is_none());
+
+        mock.assert_calls(4);
+    }
+
+    #[tokio::test]
+    async fn test_loaded_models_by_library() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(
+                    r#"{"models": [
+                        {"name": "clip-a", "library": "open_clip"},
+                        {"name": "e5", "library": "sentence_transformers"},
+                        {"name": "clip-b", "library": "open_clip"},
+                        {"name": "mobilenet", "library": "timm"}
+                    ]}"#,
+                );
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let models = client
+            .loaded_models_by_library(ModelLibrary::OpenClip)
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["clip-a", "clip-b"]);
+        assert!(models.iter().all(|m| m.library == ModelLibrary::OpenClip));
+        mock.assert();
+    }
+
+    fn mock_classification(server: &MockServer, labels: &str, probabilities: &str) {
+        let labels_body = format!(r#"{{"labels": {}}}"#, labels);
+        server.mock(move |when, then| {
+            when.method(GET)
+                .path("/model_classification_labels")
+                .query_param("name", "test-model");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(labels_body);
+        });
+
+        let classify_body = format!(
+            r#"{{"probabilities": {}, "processingTimeMs": 2.0}}"#,
+            probabilities
+        );
+        server.mock(move |when, then| {
+            when.method(POST).path("/classify_image");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(classify_body);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_classify_image_labeled() {
+        let server = MockServer::start();
+        mock_classification(&server, r#"["cat", "dog", "bird"]"#, "[[0.7, 0.2, 0.1]]");
+
+        let client = IngrainClient::new(&server.url(""), &server.url(""));
+
+        let result = client
+            .classify_image_labeled("test-model", vec!["image_url"], None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![vec![
+                ("cat".to_string(), 0.7),
+                ("dog".to_string(), 0.2),
+                ("bird".to_string(), 0.1),
+            ]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_classify_image_labeled_length_mismatch() {
+        let server = MockServer::start();
+        mock_classification(&server, r#"["cat", "dog", "bird"]"#, "[[0.7, 0.3]]");
+
+        let client = IngrainClient::new(&server.url(""), &server.url(""));
+
+        let result = client
+            .classify_image_labeled("test-model", vec!["image_url"], None)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(IngrainError::LabelMismatch {
+                labels: 3,
+                probabilities: 2
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_classify_image_top_k() {
+        let server = MockServer::start();
+        mock_classification(
+            &server,
+            r#"["cat", "dog", "bird", "fish"]"#,
+            "[[0.1, 0.5, 0.15, 0.25], [0.4, 0.3, 0.2, 0.1]]",
+        );
+
+        let client = IngrainClient::new(&server.url(""), &server.url(""));
+
+        let result = client
+            .classify_image_top_k("test-model", vec!["image_a", "image_b"], 2, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                vec![("dog".to_string(), 0.5), ("fish".to_string(), 0.25)],
+                vec![("cat".to_string(), 0.4), ("dog".to_string(), 0.3)],
+            ]
+        );
+
+        let all = client
+            .classify_image_top_k("test-model", vec!["image_a", "image_b"], 10, None)
+            .await
+            .unwrap();
+        assert_eq!(all[0].len(), 4);
+        assert!(all[0].windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_files() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_image")
+                .body_includes("data:image/png;base64,iVBORw0KGgo");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2]], "processingTimeMs": 1.5}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pixel.png");
+
+        let response = client
+            .embed_image_files("test-model", [fixture], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2]]);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_bytes() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_image")
+                .body_includes("data:image/png;base64,iVBORw0KGgo");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2]], "processingTimeMs": 1.5}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let png =
+            std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pixel.png"))
+                .unwrap();
+
+        let response = client
+            .embed_image_bytes("test-model", vec![(png, ImageMime::Png)], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2]]);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_image_files_missing_file() {
+        let client = IngrainClient::new("http://localhost:8687", "http://localhost:8686");
+
+        let result = client
+            .embed_image_files("test-model", ["does/not/exist.png"], None, None)
+            .await;
+
+        assert!(matches!(result, Err(IngrainError::Io { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_batched_preserves_order() {
+        let server = MockServer::start();
+
+        let chunks = [
+            (r#""text":["a","b"]"#, "[[1.0], [2.0]]", 200),
+            (r#""text":["c","d"]"#, "[[3.0], [4.0]]", 0),
+            (r#""text":["e"]"#, "[[5.0]]", 50),
+        ];
+        let mocks: Vec<_> = chunks
+            .iter()
+            .map(|(matcher, embeddings, delay_ms)| {
+                let body = format!(
+                    r#"{{"embeddings": {}, "processingTimeMs": 1.5}}"#,
+                    embeddings
+                );
+                server.mock(move |when, then| {
+                    when.method(POST)
+                        .path("/embed_text")
+                        .body_includes(*matcher);
+                    then.status(200)
+                        .delay(Duration::from_millis(*delay_ms))
+                        .header("Content-Type", "application/json")
+                        .body(body);
+                })
+            })
+            .collect();
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let response = client
+            .embed_text_batched("test-model", ["a", "b", "c", "d", "e"], 2, 3, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.embeddings,
+            vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![5.0]]
+        );
+        assert!((response.processing_time_ms - 4.5).abs() < 1e-6);
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_batched_rejects_wrong_chunk_count() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["a","b"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[1.0]], "processingTimeMs": 1.0}"#);
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["c"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[3.0]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let err = client
+            .embed_text_batched("test-model", ["a", "b", "c"], 2, 2, None, None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            IngrainError::UnexpectedResultCount {
+                expected: 2,
+                found: 1
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_classify_image_batched_rejects_wrong_chunk_count() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/classify_image");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"probabilities": [[0.1, 0.9]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let err = client
+            .classify_image_batched(
+                "test-model",
+                vec!["a".into(), "b".into(), "c".into()],
+                2,
+                2,
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            IngrainError::UnexpectedResultCount {
+                expected: 2,
+                found: 1
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_classify_image_batched_preserves_order() {
+        let server = MockServer::start();
+
+        let chunks = [
+            (r#""image":["a","b"]"#, "[[0.1, 0.9], [0.2, 0.8]]", 150),
+            (r#""image":["c"]"#, "[[0.3, 0.7]]", 0),
+        ];
+        let mocks: Vec<_> = chunks
+            .iter()
+            .map(|(matcher, probabilities, delay_ms)| {
+                let body = format!(
+                    r#"{{"probabilities": {}, "processingTimeMs": 2.0}}"#,
+                    probabilities
+                );
+                server.mock(move |when, then| {
+                    when.method(POST)
+                        .path("/classify_image")
+                        .body_includes(*matcher);
+                    then.status(200)
+                        .delay(Duration::from_millis(*delay_ms))
+                        .header("Content-Type", "application/json")
+                        .body(body);
+                })
+            })
+            .collect();
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let images = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let response = client
+            .classify_image_batched("test-model", images, 2, 2, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.probabilities.len(), 3);
+        assert_eq!(
+            response.probabilities,
+            vec![vec![0.1, 0.9], vec![0.2, 0.8], vec![0.3, 0.7]]
+        );
+        assert!((response.processing_time_ms - 4.0).abs() < 1e-6);
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_path_prefix_applies_to_every_endpoint() {
+        let server = MockServer::start();
+
+        let embed_mock = server.mock(|when, then| {
+            when.method(POST).path("/ingrain/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+        let health_mock = server.mock(|when, then| {
+            when.method(GET).path("/ingrain/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let base_url = format!("{}/", server.url(""));
+        let client = IngrainClient::builder()
+            .model_server_url(&base_url)
+            .inference_server_url(&base_url)
+            .path_prefix("/ingrain/")
+            .build()
+            .unwrap();
+
+        client
+            .embed_text("test-model", ["hello"], None, None)
+            .await
+            .unwrap();
+        client.model_server_health().await.unwrap();
+
+        embed_mock.assert();
+        health_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_trailing_slash_urls_produce_same_paths() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let with_slash = format!("{}/", server.url(""));
+        let without_slash = server.url("");
+
+        for url in [with_slash, without_slash] {
+            let client = IngrainClient::new(&url, &url);
+            assert!(client.model_server_health().await.is_ok());
+        }
+
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_cancelled_mid_flight() {
+        let server = MockServer::start();
+
+        let _mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .delay(Duration::from_secs(3))
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+        let token = CancellationToken::new();
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+            canceller.cancel();
+        });
+
+        let start = Instant::now();
+        let result = client
+            .embed_text_cancellable("test-model", ["hi"], None, None, &token)
+            .await;
+
+        assert!(matches!(result, Err(IngrainError::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_cancelled_between_retries() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503).body("Service Unavailable");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 5, 2000);
+        let token = CancellationToken::new();
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let start = Instant::now();
+        let result = client
+            .embed_text_cancellable("test-model", ["hi"], None, None, &token)
+            .await;
+
+        assert!(matches!(result, Err(IngrainError::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        mock.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_request_json_custom_endpoint() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/custom_endpoint")
+                .query_param("verbose", "true")
+                .header("authorization", "Bearer secret")
+                .body_includes(r#""name":"test-model""#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .auth(AuthConfig::Bearer("secret".to_string()))
+            .build()
+            .unwrap();
+
+        let body = serde_json::json!({"name": "test-model"});
+        let query = serde_json::json!({"verbose": true});
+        let response: GenericMessageResponse = client
+            .request_json(
+                Method::POST,
+                "/custom_endpoint",
+                ServerTarget::Inference,
+                Some(&body),
+                Some(&query),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.message, "ok");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_request_json_accepts_different_body_and_query_types() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/custom_endpoint")
+                .query_param("verbose", "true")
+                .body_includes(r#""name":"test-model""#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let body = serde_json::json!({"name": "test-model"});
+        let response: GenericMessageResponse = client
+            .request_json(
+                Method::POST,
+                "/custom_endpoint",
+                ServerTarget::Inference,
+                Some(&body),
+                Some(&[("verbose", "true")]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.message, "ok");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_embed_text_checked_rejects_too_many_dims() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+
+        let size_mock = model_server.mock(|when, then| {
+            when.method(GET)
+                .path("/model_embedding_size")
+                .query_param("name", "test-model");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddingSize": 384}"#);
+        });
+        let embed_mock = inference_server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        let result = client
+            .embed_text_checked("test-model", ["hello"], None, Dims::new(1024))
+            .await;
+
+        match result {
+            Err(IngrainError::InvalidDimensions { requested, max }) => {
+                assert_eq!(requested, 1024);
+                assert_eq!(max, 384);
+            }
+            other => panic!("expected InvalidDimensions error, got {:?}", other),
+        }
+        size_mock.assert();
+        embed_mock.assert_calls(0);
+
+        client
+            .embed_text_checked("test-model", ["hello"], None, Dims::new(128))
+            .await
+            .unwrap();
+        embed_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_generated_request_id_header() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health").is_true(|req| {
+                req.headers()
+                    .get("x-request-id")
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| uuid::Uuid::parse_str(value).is_ok())
+            });
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .generate_request_ids(true)
+            .build()
+            .unwrap();
+
+        client.model_server_health().await.unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_request_id_override() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/health")
+                .header("x-request-id", "trace-1234");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .generate_request_ids(true)
+            .build()
+            .unwrap();
+
+        client
+            .with_request_id("trace-1234")
+            .model_server_health()
+            .await
+            .unwrap();
+        mock.assert();
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_tracing_warns_on_each_retry() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503).body("unavailable");
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 2, 0);
+
+        let result = client.embed_text("test-model", ["hello"], None, None).await;
 
         assert!(result.is_err());
+        mock.assert_calls(3);
+        logs_assert(|lines: &[&str]| {
+            let warnings = lines
+                .iter()
+                .filter(|line| line.contains("WARN") && line.contains("retrying"))
+                .count();
+            let errors = lines
+                .iter()
+                .filter(|line| line.contains("ERROR") && line.contains("request failed"))
+                .count();
+            match (warnings, errors) {
+                (2, 1) => Ok(()),
+                other => Err(format!("expected 2 warnings and 1 error, got {:?}", other)),
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn test_load_models_reports_each_result() {
+        let server = MockServer::start();
+
+        let ok_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/load_model")
+                .is_true(|req| !String::from_utf8_lossy(req.body().as_ref()).contains("broken"));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "loaded"}"#);
+        });
+        let failing_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/load_model")
+                .body_includes(r#""name":"broken-model""#);
+            then.status(500).body("out of memory");
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let results = client
+            .load_models(
+                vec![
+                    ("model-a".to_string(), ModelLibrary::OpenClip),
+                    ("broken-model".to_string(), ModelLibrary::Timm),
+                    ("model-c".to_string(), ModelLibrary::SentenceTransformers),
+                ],
+                2,
+            )
+            .await;
+
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["model-a", "broken-model", "model-c"]);
+        assert_eq!(results[0].1.as_ref().unwrap().message, "loaded");
+        assert!(matches!(
+            results[1].1,
+            Err(IngrainError::Http { status, .. }) if status == reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        ));
+        assert_eq!(results[2].1.as_ref().unwrap().message, "loaded");
+        ok_mock.assert_calls(2);
+        failing_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_unload_all_models() {
+        let server = MockServer::start();
 
-        mock.assert();
+        let loaded_mock = server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(
+                    r#"{"models": [
+                        {"name": "model-a", "library": "open_clip"},
+                        {"name": "model-b", "library": "sentence_transformers"}
+                    ]}"#,
+                );
+        });
+        let unload_mock = server.mock(|when, then| {
+            when.method(POST).path("/unload_model");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "unloaded"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let results = client.unload_all_models().await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "model-a");
+        assert_eq!(results[1].0, "model-b");
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        loaded_mock.assert();
+        unload_mock.assert_calls(2);
     }
 
     #[tokio::test]
-    async fn test_embed_image_fails_after_retries() {
+    async fn test_max_concurrent_requests_serializes_calls() {
         let server = MockServer::start();
 
-        // Always fail
-        let _fail_mock = server.mock(|when, then| {
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#)
+                .delay(Duration::from_millis(200));
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .max_concurrent_requests(1)
+            .build()
+            .unwrap();
+
+        let started = Instant::now();
+        let (first, second) =
+            tokio::join!(client.model_server_health(), client.model_server_health());
+
+        first.unwrap();
+        second.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(400));
+        mock.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_telemetry_totals_processing_time() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.25}"#);
+        });
+        server.mock(|when, then| {
             when.method(POST).path("/embed_image");
-            then.status(500).body("Internal Error");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.2]], "processingTimeMs": 4.5}"#);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"models": []}"#);
         });
 
-        let client =
-            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 2, 10);
+        let totals = Arc::new(ProcessingTimeTotals::default());
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .inference_server_url(&server.url(""))
+            .telemetry(totals.clone())
+            .build()
+            .unwrap();
 
-        let result = client
+        for _ in 0..3 {
+            client
+                .embed_text("test-model", ["hello"], None, None)
+                .await
+                .unwrap();
+        }
+        client
             .embed_image(
-                "test-model".to_string(),
-                vec!["image_url".to_string()],
+                "test-model",
+                ["https://example.com/a.png"],
                 None,
                 None,
                 None,
             )
+            .await
+            .unwrap();
+        client.loaded_models().await.unwrap();
+
+        assert_eq!(totals.calls("embed_text"), 3);
+        assert_eq!(totals.total_ms("embed_text"), 3.75);
+        assert_eq!(totals.total_ms("embed_image"), 4.5);
+        assert_eq!(totals.calls("loaded_models"), 0);
+        assert_eq!(totals.overall_ms(), 8.25);
+    }
+
+    #[tokio::test]
+    async fn test_requests_per_second_spaces_out_calls() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "ok"}"#);
+        });
+
+        let client = IngrainClient::builder()
+            .model_server_url(&server.url(""))
+            .requests_per_second(10.0)
+            .build()
+            .unwrap();
+
+        // The first call goes out at once, the other four 100ms apart.
+        let started = Instant::now();
+        let results = futures::future::join_all((0..5).map(|_| client.model_server_health())).await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert!(started.elapsed() >= Duration::from_millis(400));
+        mock.assert_calls(5);
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline_aborts_slow_call() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1]], "processingTimeMs": 1.0}"#)
+                .delay(Duration::from_secs(2));
+        });
+
+        let client =
+            IngrainClient::new_with_retries("http://localhost:8687", &server.url(""), 5, 0);
+
+        let started = Instant::now();
+        let result = client
+            .with_deadline(started + Duration::from_millis(100))
+            .embed_text("test-model", ["hello"], None, None)
             .await;
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(IngrainError::DeadlineExceeded)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        mock.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_deadline_stops_before_attempts_run_out() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/embed_text");
+            then.status(503)
+                .body("unavailable")
+                .delay(Duration::from_millis(100));
+        });
+
+        let client = IngrainClient::builder()
+            .inference_server_url(&server.url(""))
+            .retries(20)
+            .retry_delay_ms(50)
+            .retry_deadline(Duration::from_millis(400))
+            .build()
+            .unwrap();
+
+        let started = Instant::now();
+        let result = client.embed_text("test-model", ["hello"], None, None).await;
+        let elapsed = started.elapsed();
+
+        match result {
+            Err(IngrainError::RetriesExhausted { attempts, .. }) => {
+                assert!((2..=4).contains(&attempts), "made {} attempts", attempts);
+                mock.assert_calls(attempts as usize);
+            }
+            other => panic!("expected RetriesExhausted error, got {:?}", other),
+        }
+        assert!(elapsed < Duration::from_millis(700), "took {:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_retry_override_disables_retries() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/load_model");
+            then.status(503).body("unavailable");
+        });
+
+        let client =
+            IngrainClient::new_with_retries(&server.url(""), "http://localhost:8686", 3, 0);
+
+        let result = client
+            .with_retry_override(RetryOverride {
+                retries: 0,
+                delay_ms: 0,
+            })
+            .load_model("test-model", ModelLibrary::OpenClip)
+            .await;
+
+        match result {
+            Err(IngrainError::Http { status, .. }) => {
+                assert_eq!(status, reqwest::StatusCode::SERVICE_UNAVAILABLE)
+            }
+            other => panic!("expected Http error, got {:?}", other),
+        }
+        mock.assert_calls(1);
+
+        let result = client
+            .load_model("test-model", ModelLibrary::OpenClip)
+            .await;
+        assert!(matches!(
+            result,
+            Err(IngrainError::RetriesExhausted { attempts: 4, .. })
+        ));
+        mock.assert_calls(5);
+    }
+
+    #[tokio::test]
+    async fn test_repository_models_paged_sends_limit_and_offset() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/repository_models")
+                .query_param("limit", "2")
+                .query_param("offset", "4");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(
+                    r#"{"models": [{"name": "a", "state": "READY"}, {"name": "b", "state": "AVAILABLE"}],
+                        "total": 7, "nextOffset": 6}"#,
+                );
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let page = client
+            .repository_models_paged(RepositoryPageRequest {
+                limit: Some(2),
+                offset: Some(4),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(page.models.len(), 2);
+        assert_eq!(page.total, Some(7));
+        assert_eq!(page.next_offset, Some(6));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_delete_model_if_unloaded_refuses_loaded_model() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"models": [{"name": "test-model", "library": "timm"}]}"#);
+        });
+        let delete = server.mock(|when, then| {
+            when.method(POST).path("/delete_model");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"message": "deleted"}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client.delete_model_if_unloaded("test-model").await;
+        assert!(matches!(result, Err(IngrainError::ModelInUse { name }) if name == "test-model"));
+        delete.assert_calls(0);
+
+        let response = client
+            .delete_model_if_unloaded("other-model")
+            .await
+            .unwrap();
+        assert_eq!(response.message, "deleted");
+        delete.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_loaded_models_with_metrics_joins_by_name() {
+        let model_server = MockServer::start();
+        let inference_server = MockServer::start();
+
+        model_server.mock(|when, then| {
+            when.method(GET).path("/loaded_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"models": [{"name": "busy-model", "library": "timm"}, {"name": "idle-model", "library": "open_clip"}]}"#);
+        });
+        inference_server.mock(|when, then| {
+            when.method(GET).path("/metrics");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"modelStats": [{"name": "busy-model", "version": "1", "inferenceStats": {}, "inferenceCount": "7"}]}"#);
+        });
+
+        let client = IngrainClient::new(&model_server.url(""), &inference_server.url(""));
+
+        let models = client.loaded_models_with_metrics().await.unwrap();
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].0.name, "busy-model");
+        assert_eq!(models[0].1.as_ref().unwrap().inference_count, Some(7));
+        assert_eq!(models[1].0.name, "idle-model");
+        assert!(models[1].1.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reload_model_unloads_then_loads() {
+        let server = MockServer::start();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let unload_calls = calls.clone();
+        server.mock(move |when, then| {
+            when.method(POST)
+                .path("/unload_model")
+                .body_includes(r#""name":"test-model""#);
+            then.respond_with(move |_req: &httpmock::HttpMockRequest| {
+                unload_calls.lock().unwrap().push("unload");
+                HttpMockResponse::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(r#"{"message": "unloaded"}"#)
+                    .build()
+            });
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/unload_model")
+                .body_includes(r#""name":"idle-model""#);
+            then.status(404)
+                .body(r#"{"detail": "Model idle-model is not loaded"}"#);
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/unload_model")
+                .body_includes(r#""name":"missing-model""#);
+            then.status(404)
+                .body(r#"{"detail": "No route for this path"}"#);
+        });
+        let load_calls = calls.clone();
+        let load = server.mock(move |when, then| {
+            when.method(POST).path("/load_model");
+            then.respond_with(move |_req: &httpmock::HttpMockRequest| {
+                load_calls.lock().unwrap().push("load");
+                HttpMockResponse::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(r#"{"message": "loaded"}"#)
+                    .build()
+            });
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let reload = client
+            .reload_model("test-model", ModelLibrary::SentenceTransformers, None)
+            .await
+            .unwrap();
+        assert_eq!(reload.unload.unwrap().message, "unloaded");
+        assert_eq!(reload.load.message, "loaded");
+        assert_eq!(*calls.lock().unwrap(), ["unload", "load"]);
+
+        let reload = client
+            .reload_model("idle-model", ModelLibrary::SentenceTransformers, None)
+            .await
+            .unwrap();
+        assert!(reload.unload.is_none());
+        assert_eq!(reload.load.message, "loaded");
+        load.assert_calls(2);
+
+        let err = client
+            .reload_model("missing-model", ModelLibrary::SentenceTransformers, None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, IngrainError::Http { status, .. } if status == StatusCode::NOT_FOUND)
+        );
+        load.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_model_metadata_fetches_both() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/model_embedding_size")
+                .query_param("name", "clip");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddingSize": 512}"#);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/model_classification_labels")
+                .query_param("name", "clip");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"labels": ["cat", "dog"]}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let metadata = client.model_metadata("clip").await.unwrap();
+        assert_eq!(metadata.embedding_size, Some(512));
+        assert_eq!(
+            metadata.labels,
+            Some(vec!["cat".to_string(), "dog".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_model_metadata_tolerates_missing_embedding_size() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/model_embedding_size");
+            then.status(404).body("not found");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/model_classification_labels");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"labels": ["cat"]}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let metadata = client.model_metadata("resnet").await.unwrap();
+        assert_eq!(metadata.embedding_size, None);
+        assert_eq!(metadata.labels, Some(vec!["cat".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_repository_models_if_changed_uses_etag() {
+        let server = MockServer::start();
+
+        let not_modified = server.mock(|when, then| {
+            when.method(GET)
+                .path("/repository_models")
+                .header("if-none-match", "\"v1\"");
+            then.status(304).header("ETag", "\"v1\"");
+        });
+        let first = server.mock(|when, then| {
+            when.method(GET)
+                .path("/repository_models")
+                .header_missing("if-none-match");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .header("ETag", "\"v1\"")
+                .body(r#"{"models": [{"name": "m", "state": "READY"}]}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let models = client.repository_models_if_changed().await.unwrap();
+        assert_eq!(models.unwrap().models.len(), 1);
+        let models = client.clone().repository_models_if_changed().await.unwrap();
+        assert!(models.is_none());
+
+        first.assert_calls(1);
+        not_modified.assert_calls(1);
+    }
+
+    fn repository_response(state: &str) -> HttpMockResponse {
+        HttpMockResponse::builder()
+            .status(200)
+            .header("Content-Type", "application/json")
+            .body(format!(
+                r#"{{"models": [{{"name": "test-model", "state": "{}"}}]}}"#,
+                state
+            ))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_model_becomes_ready() {
+        let server = MockServer::start();
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let counter = polls.clone();
+        let mock = server.mock(move |when, then| {
+            when.method(GET).path("/repository_models");
+            then.respond_with(move |_| {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    repository_response("LOADING")
+                } else {
+                    repository_response("READY")
+                }
+            });
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client
+            .wait_for_model(
+                "test-model",
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await;
+
+        assert!(result.is_ok());
+        mock.assert_calls(3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_model_times_out() {
+        let server = MockServer::start();
+
+        let _mock = server.mock(|when, then| {
+            when.method(GET).path("/repository_models");
+            then.respond_with(|_| repository_response("LOADING"));
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client
+            .wait_for_model(
+                "test-model",
+                Duration::from_millis(50),
+                Duration::from_millis(10),
+            )
+            .await;
+
+        match result {
+            Err(IngrainError::ModelNotReady { name, state }) => {
+                assert_eq!(name, "test-model");
+                assert_eq!(state, ModelState::Loading);
+            }
+            other => panic!("expected ModelNotReady error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_model_fails_fast_when_unavailable() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/repository_models");
+            then.respond_with(|_| repository_response("UNAVAILABLE"));
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client
+            .wait_for_model(
+                "test-model",
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(IngrainError::ModelNotReady {
+                state: ModelState::Unavailable,
+                ..
+            })
+        ));
+        mock.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_model_missing() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/repository_models");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"models": []}"#);
+        });
+
+        let client = IngrainClient::new(&server.url(""), "http://localhost:8686");
+
+        let result = client
+            .wait_for_model(
+                "test-model",
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await;
+
+        assert!(matches!(result, Err(IngrainError::ModelNotFound { .. })));
+        mock.assert_calls(1);
     }
 
     #[tokio::test]
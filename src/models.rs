@@ -1,5 +1,82 @@
-use serde::{Deserialize, Serialize};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
+use std::num::NonZeroU16;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::error::IngrainError;
+
+/// The inference server encodes its counters as JSON strings; accept both
+/// strings and plain numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(u64),
+    String(String),
+}
+
+impl NumberOrString {
+    fn into_u64<E: serde::de::Error>(self) -> Result<u64, E> {
+        match self {
+            NumberOrString::Number(n) => Ok(n),
+            NumberOrString::String(s) => s.parse().map_err(E::custom),
+        }
+    }
+}
+
+fn u64_from_number_or_string<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    NumberOrString::deserialize(deserializer)?.into_u64()
+}
+
+fn opt_u64_from_number_or_string<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<NumberOrString>::deserialize(deserializer)?
+        .map(NumberOrString::into_u64)
+        .transpose()
+}
+
+/// The number of embedding dimensions to request. Zero is rejected when the
+/// value is built, rather than by the server; serializes as a plain number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Dims(NonZeroU16);
+
+impl Dims {
+    /// Returns `None` for zero, so `Dims::new(n)` can be passed straight
+    /// in as the `n_dims` argument of the embed methods.
+    pub const fn new(dims: u16) -> Option<Dims> {
+        match NonZeroU16::new(dims) {
+            Some(dims) => Some(Dims(dims)),
+            None => None,
+        }
+    }
+
+    pub const fn get(self) -> u16 {
+        self.0.get()
+    }
+}
+
+impl From<NonZeroU16> for Dims {
+    fn from(dims: NonZeroU16) -> Self {
+        Dims(dims)
+    }
+}
+
+impl fmt::Display for Dims {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,7 +85,7 @@ pub struct EmbeddingRequest {
     pub text: Option<Vec<String>>,
     pub image: Option<Vec<String>>,
     pub normalize: Option<bool>,
-    pub n_dims: Option<u16>,
+    pub n_dims: Option<Dims>,
     pub image_download_headers: Option<HashMap<String, String>>,
 }
 
@@ -18,7 +95,7 @@ pub struct TextEmbeddingRequest {
     pub name: String,
     pub text: Vec<String>,
     pub normalize: Option<bool>,
-    pub n_dims: Option<u16>,
+    pub n_dims: Option<Dims>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,7 +104,7 @@ pub struct ImageEmbeddingRequest {
     pub name: String,
     pub image: Vec<String>,
     pub normalize: Option<bool>,
-    pub n_dims: Option<u16>,
+    pub n_dims: Option<Dims>,
     pub image_download_headers: Option<HashMap<String, String>>,
 }
 
@@ -39,12 +116,94 @@ pub struct ImageClassificationRequest {
     pub image_download_headers: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+/// Serialized in `snake_case`. Libraries this crate does not know about yet
+/// deserialize into `Other` and serialize back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ModelLibrary {
     OpenClip,
     SentenceTransformers,
     Timm,
+    Other(String),
+}
+
+impl ModelLibrary {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModelLibrary::OpenClip => "open_clip",
+            ModelLibrary::SentenceTransformers => "sentence_transformers",
+            ModelLibrary::Timm => "timm",
+            ModelLibrary::Other(library) => library,
+        }
+    }
+
+    /// Guesses the library from the naming conventions of model names:
+    ///
+    /// - `hf_hub:timm/...`, `hf-hub:timm/...` and `timm/...` are Timm models.
+    /// - Any other `hf-hub:` or `hf_hub:` name is an OpenCLIP model, e.g.
+    ///   `hf-hub:laion/CLIP-ViT-B-32-laion2B-s34B-b79K`.
+    /// - A plain Hugging Face `owner/model` id such as `intfloat/e5-small-v2`
+    ///   is a Sentence Transformers model.
+    ///
+    /// Anything else returns `None`.
+    pub fn infer_from_name(name: &str) -> Option<ModelLibrary> {
+        let (hub, repo) = match name
+            .strip_prefix("hf-hub:")
+            .or_else(|| name.strip_prefix("hf_hub:"))
+        {
+            Some(repo) => (true, repo),
+            None => (false, name),
+        };
+
+        if repo.starts_with("timm/") {
+            Some(ModelLibrary::Timm)
+        } else if hub {
+            Some(ModelLibrary::OpenClip)
+        } else if !name.contains(':') && name.split('/').filter(|s| !s.is_empty()).count() == 2 {
+            Some(ModelLibrary::SentenceTransformers)
+        } else {
+            None
+        }
+    }
+
+    fn from_wire(library: String) -> Self {
+        match library.as_str() {
+            "open_clip" => ModelLibrary::OpenClip,
+            "sentence_transformers" => ModelLibrary::SentenceTransformers,
+            "timm" => ModelLibrary::Timm,
+            _ => ModelLibrary::Other(library),
+        }
+    }
+}
+
+impl fmt::Display for ModelLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses the `snake_case` wire names. Unlike deserialization, unknown names
+/// are rejected rather than mapped to `Other`.
+impl FromStr for ModelLibrary {
+    type Err = IngrainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match ModelLibrary::from_wire(s.to_string()) {
+            ModelLibrary::Other(library) => Err(IngrainError::UnknownModelLibrary(library)),
+            library => Ok(library),
+        }
+    }
+}
+
+impl Serialize for ModelLibrary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ModelLibrary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(ModelLibrary::from_wire)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,11 +222,50 @@ pub struct ModelMetadataRequest {
     pub name: String,
 }
 
+/// Structured error body returned by the servers on failure, e.g.
+/// `{"detail": "model not found"}`. FastAPI validation errors put a list in
+/// `detail`, so it is kept as raw JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    #[serde(default)]
+    pub detail: Option<serde_json::Value>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl ApiErrorBody {
+    /// The human-readable error, preferring `detail` over `message`.
+    pub fn summary(&self) -> Option<String> {
+        match &self.detail {
+            Some(serde_json::Value::String(detail)) => Some(detail.clone()),
+            Some(serde_json::Value::Null) | None => self.message.clone(),
+            Some(detail) => Some(detail.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GenericMessageResponse {
     pub message: String,
 }
 
+/// What [`IngrainClient::reload_model`](crate::IngrainClient::reload_model)
+/// did.
+#[derive(Debug)]
+pub struct ModelReload {
+    /// The unload response, or `None` when the model was not loaded.
+    pub unload: Option<GenericMessageResponse>,
+    pub load: GenericMessageResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerInfoResponse {
+    pub version: String,
+    /// Any other fields the server reports.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoadedModel {
     pub name: String,
@@ -79,10 +277,77 @@ pub struct LoadedModelResponse {
     pub models: Vec<LoadedModel>,
 }
 
+/// State of a model in the server's repository. The server reports states as
+/// upper-case strings; a loaded model is reported as `READY`, and one that
+/// is not loaded or failed to load as `UNAVAILABLE`. Any other string,
+/// including a known state in another case, deserializes into `Unknown` and
+/// serializes back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelState {
+    Available,
+    Loading,
+    Loaded,
+    Unloading,
+    Failed,
+    Unavailable,
+    Unknown(String),
+}
+
+impl ModelState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModelState::Available => "AVAILABLE",
+            ModelState::Loading => "LOADING",
+            ModelState::Loaded => "READY",
+            ModelState::Unloading => "UNLOADING",
+            ModelState::Failed => "FAILED",
+            ModelState::Unavailable => "UNAVAILABLE",
+            ModelState::Unknown(state) => state,
+        }
+    }
+
+    fn from_wire(state: String) -> Self {
+        match state.as_str() {
+            "AVAILABLE" => ModelState::Available,
+            "LOADING" => ModelState::Loading,
+            "READY" => ModelState::Loaded,
+            "UNLOADING" => ModelState::Unloading,
+            "FAILED" => ModelState::Failed,
+            "UNAVAILABLE" => ModelState::Unavailable,
+            _ => ModelState::Unknown(state),
+        }
+    }
+}
+
+impl fmt::Display for ModelState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ModelState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ModelState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(ModelState::from_wire)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RepositoryModel {
     pub name: String,
-    pub state: String,
+    pub state: ModelState,
+}
+
+impl RepositoryModel {
+    /// Whether the model is loaded and can serve requests.
+    pub fn is_ready(&self) -> bool {
+        self.state == ModelState::Loaded
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -90,16 +355,42 @@ pub struct RepositoryModelResponse {
     pub models: Vec<RepositoryModel>,
 }
 
+/// Selects one page of the model repository. Unset fields are left to the
+/// server's defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RepositoryPageRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+}
+
+/// One page of the model repository. `total` and `next_offset` are only set
+/// when the server reports them; a missing `next_offset` means there are no
+/// more pages.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryModelPage {
+    pub models: Vec<RepositoryModel>,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub next_offset: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InferenceStats {
-    pub count: Option<String>,
-    pub ns: Option<String>,
+    #[serde(default, deserialize_with = "opt_u64_from_number_or_string")]
+    pub count: Option<u64>,
+    #[serde(default, deserialize_with = "opt_u64_from_number_or_string")]
+    pub ns: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchStats {
-    pub batch_size: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub batch_size: u64,
     pub compute_input: InferenceStats,
     pub compute_infer: InferenceStats,
     pub compute_output: InferenceStats,
@@ -112,8 +403,10 @@ pub struct ModelStats {
     pub version: String,
     pub inference_stats: HashMap<String, InferenceStats>,
     pub last_inference: Option<String>,
-    pub inference_count: Option<String>,
-    pub execution_count: Option<String>,
+    #[serde(default, deserialize_with = "opt_u64_from_number_or_string")]
+    pub inference_count: Option<u64>,
+    #[serde(default, deserialize_with = "opt_u64_from_number_or_string")]
+    pub execution_count: Option<u64>,
     pub batch_stats: Option<Vec<BatchStats>>,
 }
 
@@ -123,9 +416,160 @@ pub struct MetricsResponse {
     pub model_stats: Vec<ModelStats>,
 }
 
+impl ModelStats {
+    /// Mean time spent in model execution per batch, from `computeInfer`
+    /// across all batch sizes. `None` when nothing has been executed.
+    pub fn average_infer_latency(&self) -> Option<Duration> {
+        let batch_stats = self.batch_stats.as_ref()?;
+        let (count, ns) = batch_stats.iter().fold((0u64, 0u64), |(count, ns), stats| {
+            (
+                count + stats.compute_infer.count.unwrap_or(0),
+                ns + stats.compute_infer.ns.unwrap_or(0),
+            )
+        });
+        if count == 0 {
+            return None;
+        }
+        Some(Duration::from_nanos(ns / count))
+    }
+
+    /// Mean number of inferences per model execution.
+    pub fn average_batch_size(&self) -> Option<f64> {
+        let inferences = self.inference_count?;
+        let executions = self.execution_count?;
+        if executions == 0 {
+            return None;
+        }
+        Some(inferences as f64 / executions as f64)
+    }
+}
+
+impl MetricsResponse {
+    pub fn by_name(&self, name: &str) -> Option<&ModelStats> {
+        self.model_stats.iter().find(|stats| stats.name == name)
+    }
+}
+
+/// Embeddings as a list of float lists, or packed into little-endian bytes:
+/// `{"dtype": "float16", "shape": [rows, dims], "data": "<base64>"}`, which
+/// saves bandwidth. `float32` is always supported and `float16` with the
+/// `f16` feature.
+enum EncodedOrFloats {
+    Floats(Vec<Vec<f32>>),
+    Encoded {
+        dtype: String,
+        shape: [usize; 2],
+        data: String,
+    },
+}
+
+/// Picks the form from the first token instead of buffering the value to try
+/// each in turn, so float lists are read directly and a bad value keeps its
+/// JSON path in the error.
+impl<'de> Deserialize<'de> for EncodedOrFloats {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Encoded {
+            dtype: String,
+            shape: [usize; 2],
+            data: String,
+        }
+
+        struct EncodedOrFloatsVisitor;
+
+        impl<'de> Visitor<'de> for EncodedOrFloatsVisitor {
+            type Value = EncodedOrFloats;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a list of float lists or an encoded embedding object")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(row) = seq.next_element::<Vec<f32>>()? {
+                    rows.push(row);
+                }
+                Ok(EncodedOrFloats::Floats(rows))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                let Encoded { dtype, shape, data } =
+                    Encoded::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(EncodedOrFloats::Encoded { dtype, shape, data })
+            }
+        }
+
+        deserializer.deserialize_any(EncodedOrFloatsVisitor)
+    }
+}
+
+impl EncodedOrFloats {
+    fn into_rows<E: serde::de::Error>(self) -> Result<Vec<Vec<f32>>, E> {
+        let (dtype, [rows, dims], data) = match self {
+            EncodedOrFloats::Floats(rows) => return Ok(rows),
+            EncodedOrFloats::Encoded { dtype, shape, data } => (dtype, shape, data),
+        };
+        let bytes = STANDARD.decode(data).map_err(E::custom)?;
+        let width = match dtype.as_str() {
+            "float32" => 4,
+            "float16" if cfg!(feature = "f16") => 2,
+            "float16" => {
+                return Err(E::custom(
+                    "float16 embeddings need the `f16` feature of ingrain_rs",
+                ));
+            }
+            other => {
+                return Err(E::custom(format!(
+                    "unsupported embedding dtype `{}`",
+                    other
+                )));
+            }
+        };
+        if rows.checked_mul(dims).and_then(|n| n.checked_mul(width)) != Some(bytes.len()) {
+            return Err(E::custom(format!(
+                "{} bytes of {} do not fit the shape [{}, {}]",
+                bytes.len(),
+                dtype,
+                rows,
+                dims
+            )));
+        }
+        let values = bytes.chunks_exact(width).map(|value| match value {
+            [a, b, c, d] => f32::from_le_bytes([*a, *b, *c, *d]),
+            #[cfg(feature = "f16")]
+            [a, b] => half::f16::from_le_bytes([*a, *b]).to_f32(),
+            _ => unreachable!("chunks have the width of the dtype"),
+        });
+        if dims == 0 {
+            return Ok(vec![Vec::new(); rows]);
+        }
+        let values: Vec<f32> = values.collect();
+        Ok(values.chunks(dims).map(<[f32]>::to_vec).collect())
+    }
+}
+
+fn embeddings_from_floats_or_encoded<'de, D>(deserializer: D) -> Result<Vec<Vec<f32>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    EncodedOrFloats::deserialize(deserializer)?.into_rows()
+}
+
+fn opt_embeddings_from_floats_or_encoded<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<Vec<f32>>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<EncodedOrFloats>::deserialize(deserializer)?
+        .map(EncodedOrFloats::into_rows)
+        .transpose()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TextEmbeddingResponse {
+    #[serde(deserialize_with = "embeddings_from_floats_or_encoded")]
     pub embeddings: Vec<Vec<f32>>,
     pub processing_time_ms: f32,
 }
@@ -133,10 +577,72 @@ pub struct TextEmbeddingResponse {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImageEmbeddingResponse {
+    #[serde(deserialize_with = "embeddings_from_floats_or_encoded")]
     pub embeddings: Vec<Vec<f32>>,
     pub processing_time_ms: f32,
 }
 
+/// Rounded to the nanosecond; negative or NaN times become zero.
+fn millis_to_duration(ms: f32) -> Duration {
+    Duration::from_nanos((f64::from(ms) * 1_000_000.0).round() as u64)
+}
+
+impl TextEmbeddingResponse {
+    /// Checks that every embedding has the same length and returns it.
+    pub fn validate_uniform_dims(&self) -> Result<usize, IngrainError> {
+        crate::embeddings::uniform_dims(&self.embeddings)
+    }
+
+    /// L2-normalizes every embedding in place, for models the server cannot
+    /// normalize. Rows with a norm of zero are left unchanged.
+    pub fn normalize_embeddings(&mut self) {
+        for row in &mut self.embeddings {
+            crate::embeddings::l2_normalize(row);
+        }
+    }
+
+    /// `processing_time_ms` as a `Duration`.
+    pub fn processing_time(&self) -> Duration {
+        millis_to_duration(self.processing_time_ms)
+    }
+}
+
+impl ImageEmbeddingResponse {
+    /// Checks that every embedding has the same length and returns it.
+    pub fn validate_uniform_dims(&self) -> Result<usize, IngrainError> {
+        crate::embeddings::uniform_dims(&self.embeddings)
+    }
+
+    /// L2-normalizes every embedding in place, for models the server cannot
+    /// normalize. Rows with a norm of zero are left unchanged.
+    pub fn normalize_embeddings(&mut self) {
+        for row in &mut self.embeddings {
+            crate::embeddings::l2_normalize(row);
+        }
+    }
+
+    /// `processing_time_ms` as a `Duration`.
+    pub fn processing_time(&self) -> Duration {
+        millis_to_duration(self.processing_time_ms)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl TextEmbeddingResponse {
+    /// Stacks the embeddings into an `(n, dims)` array.
+    pub fn to_array2(&self) -> Result<ndarray::Array2<f32>, IngrainError> {
+        crate::embeddings::to_array2(&self.embeddings)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl ImageEmbeddingResponse {
+    /// Stacks the embeddings into an `(n, dims)` array.
+    pub fn to_array2(&self) -> Result<ndarray::Array2<f32>, IngrainError> {
+        crate::embeddings::to_array2(&self.embeddings)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImageClassificationResponse {
@@ -144,14 +650,30 @@ pub struct ImageClassificationResponse {
     pub processing_time_ms: f32,
 }
 
+impl ImageClassificationResponse {
+    /// `processing_time_ms` as a `Duration`.
+    pub fn processing_time(&self) -> Duration {
+        millis_to_duration(self.processing_time_ms)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EmbeddingResponse {
+    #[serde(default, deserialize_with = "opt_embeddings_from_floats_or_encoded")]
     pub text_embeddings: Option<Vec<Vec<f32>>>,
+    #[serde(default, deserialize_with = "opt_embeddings_from_floats_or_encoded")]
     pub image_embeddings: Option<Vec<Vec<f32>>>,
     pub processing_time_ms: f32,
 }
 
+impl EmbeddingResponse {
+    /// `processing_time_ms` as a `Duration`.
+    pub fn processing_time(&self) -> Duration {
+        millis_to_duration(self.processing_time_ms)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModelClassificationLabelsResponse {
     pub labels: Vec<String>,
@@ -162,3 +684,364 @@ pub struct ModelClassificationLabelsResponse {
 pub struct ModelEmbeddingDimsResponse {
     pub embedding_size: u64,
 }
+
+/// What [`IngrainClient::model_metadata`](crate::IngrainClient::model_metadata)
+/// found out about a model; a field is `None` when the model does not have it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelMetadata {
+    pub embedding_size: Option<u64>,
+    pub labels: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = r#"{
+        "modelStats": [{
+            "name": "intfloat_e5-small-v2",
+            "version": "1",
+            "lastInference": "1718000000000",
+            "inferenceCount": "42",
+            "executionCount": 40,
+            "inferenceStats": {
+                "success": {"count": "42", "ns": "8400000"},
+                "fail": {}
+            },
+            "batchStats": [{
+                "batchSize": "1",
+                "computeInput": {"count": "38", "ns": 380000},
+                "computeInfer": {"count": "38", "ns": "7600000"},
+                "computeOutput": {"count": "38", "ns": "190000"}
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn test_metrics_numeric_strings() {
+        let metrics: MetricsResponse = serde_json::from_str(METRICS).unwrap();
+        let stats = &metrics.model_stats[0];
+
+        assert_eq!(stats.inference_count, Some(42));
+        assert_eq!(stats.execution_count, Some(40));
+        assert_eq!(stats.inference_stats["success"].count, Some(42));
+        assert_eq!(stats.inference_stats["success"].ns, Some(8_400_000));
+        assert_eq!(stats.inference_stats["fail"].count, None);
+
+        let batch = &stats.batch_stats.as_ref().unwrap()[0];
+        assert_eq!(batch.batch_size, 1);
+        assert_eq!(batch.compute_input.ns, Some(380_000));
+        assert_eq!(batch.compute_infer.ns, Some(7_600_000));
+    }
+
+    #[test]
+    fn test_metrics_derived_values() {
+        let metrics: MetricsResponse = serde_json::from_str(METRICS).unwrap();
+        let stats = metrics.by_name("intfloat_e5-small-v2").unwrap();
+
+        assert_eq!(
+            stats.average_infer_latency(),
+            Some(Duration::from_nanos(200_000))
+        );
+        assert_eq!(stats.average_batch_size(), Some(42.0 / 40.0));
+        assert!(metrics.by_name("missing").is_none());
+    }
+
+    #[test]
+    fn test_metrics_derived_values_without_executions() {
+        let stats: ModelStats = serde_json::from_str(
+            r#"{
+                "name": "idle",
+                "version": "1",
+                "inferenceCount": "0",
+                "executionCount": "0",
+                "inferenceStats": {},
+                "batchStats": [{
+                    "batchSize": "1",
+                    "computeInput": {},
+                    "computeInfer": {"count": "0", "ns": "0"},
+                    "computeOutput": {}
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(stats.average_infer_latency(), None);
+        assert_eq!(stats.average_batch_size(), None);
+    }
+
+    #[test]
+    fn test_model_library_known_variants() {
+        assert_eq!(
+            serde_json::to_string(&ModelLibrary::SentenceTransformers).unwrap(),
+            r#""sentence_transformers""#
+        );
+        assert_eq!(
+            serde_json::from_str::<ModelLibrary>(r#""open_clip""#).unwrap(),
+            ModelLibrary::OpenClip
+        );
+    }
+
+    #[test]
+    fn test_model_library_unknown_round_trips() {
+        let loaded: LoadedModelResponse = serde_json::from_str(
+            r#"{"models": [
+                {"name": "a", "library": "timm"},
+                {"name": "b", "library": "some_future_lib"}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(loaded.models[0].library, ModelLibrary::Timm);
+        assert_eq!(
+            loaded.models[1].library,
+            ModelLibrary::Other("some_future_lib".to_string())
+        );
+        assert_eq!(
+            serde_json::to_string(&loaded.models[1].library).unwrap(),
+            r#""some_future_lib""#
+        );
+    }
+
+    #[test]
+    fn test_model_library_display_from_str_round_trip() {
+        for library in [
+            ModelLibrary::OpenClip,
+            ModelLibrary::SentenceTransformers,
+            ModelLibrary::Timm,
+        ] {
+            let name = library.to_string();
+            assert_eq!(name, serde_json::to_value(&library).unwrap());
+            assert_eq!(name.parse::<ModelLibrary>().unwrap(), library);
+        }
+    }
+
+    #[test]
+    fn test_model_library_from_str_unknown() {
+        match "pytorch".parse::<ModelLibrary>() {
+            Err(IngrainError::UnknownModelLibrary(library)) => assert_eq!(library, "pytorch"),
+            other => panic!("expected UnknownModelLibrary error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_metrics_invalid_number() {
+        let result = serde_json::from_str::<InferenceStats>(r#"{"count": "many"}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_uniform_dims() {
+        let response: TextEmbeddingResponse = serde_json::from_str(
+            r#"{"embeddings": [[0.1, 0.2], [0.3, 0.4]], "processingTimeMs": 1.0}"#,
+        )
+        .unwrap();
+        assert_eq!(response.validate_uniform_dims().unwrap(), 2);
+
+        let ragged: ImageEmbeddingResponse =
+            serde_json::from_str(r#"{"embeddings": [[0.1, 0.2], [0.3]], "processingTimeMs": 1.0}"#)
+                .unwrap();
+        assert!(matches!(
+            ragged.validate_uniform_dims(),
+            Err(IngrainError::RaggedEmbeddings { row: 1, .. })
+        ));
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_text_embedding_response_to_array2() {
+        let response: TextEmbeddingResponse = serde_json::from_str(
+            r#"{"embeddings": [[0.1, 0.2], [0.3, 0.4], [0.5, 0.6]], "processingTimeMs": 1.0}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.to_array2().unwrap().dim(), (3, 2));
+    }
+
+    #[test]
+    fn test_model_state_known_states() {
+        let cases = [
+            ("AVAILABLE", ModelState::Available),
+            ("UNAVAILABLE", ModelState::Unavailable),
+            ("LOADING", ModelState::Loading),
+            ("READY", ModelState::Loaded),
+            ("UNLOADING", ModelState::Unloading),
+            ("FAILED", ModelState::Failed),
+        ];
+        for (wire, expected) in cases {
+            let state: ModelState = serde_json::from_str(&format!(r#""{}""#, wire)).unwrap();
+            assert_eq!(state, expected, "state {}", wire);
+            assert_eq!(state.as_str(), wire);
+        }
+    }
+
+    #[test]
+    fn test_model_state_other_spellings_round_trip() {
+        for wire in ["ready", "LOADED", "ERROR", "Unavailable"] {
+            let state: ModelState = serde_json::from_str(&format!(r#""{}""#, wire)).unwrap();
+            assert_eq!(state, ModelState::Unknown(wire.to_string()));
+            assert_eq!(
+                serde_json::to_string(&state).unwrap(),
+                format!(r#""{}""#, wire)
+            );
+        }
+    }
+
+    #[test]
+    fn test_model_state_unknown_round_trips() {
+        let model: RepositoryModel =
+            serde_json::from_str(r#"{"name": "m", "state": "QUARANTINED"}"#).unwrap();
+        assert_eq!(model.state, ModelState::Unknown("QUARANTINED".to_string()));
+        assert!(!model.is_ready());
+        assert_eq!(
+            serde_json::to_string(&model.state).unwrap(),
+            r#""QUARANTINED""#
+        );
+
+        let model: RepositoryModel =
+            serde_json::from_str(r#"{"name": "m", "state": "READY"}"#).unwrap();
+        assert!(model.is_ready());
+    }
+
+    #[test]
+    fn test_api_error_body_summary() {
+        let body: ApiErrorBody = serde_json::from_str(r#"{"detail": "model not found"}"#).unwrap();
+        assert_eq!(body.summary().as_deref(), Some("model not found"));
+
+        let body: ApiErrorBody = serde_json::from_str(r#"{"message": "boom"}"#).unwrap();
+        assert_eq!(body.summary().as_deref(), Some("boom"));
+
+        let body: ApiErrorBody =
+            serde_json::from_str(r#"{"detail": [{"msg": "field required"}]}"#).unwrap();
+        assert_eq!(
+            body.summary().as_deref(),
+            Some(r#"[{"msg":"field required"}]"#)
+        );
+    }
+
+    #[test]
+    fn test_model_library_infer_from_name() {
+        let cases = [
+            (
+                "intfloat/e5-small-v2",
+                Some(ModelLibrary::SentenceTransformers),
+            ),
+            (
+                "hf-hub:laion/CLIP-ViT-B-32-laion2B-s34B-b79K",
+                Some(ModelLibrary::OpenClip),
+            ),
+            (
+                "hf_hub:timm/mobilenetv4_conv_medium.e250_r384_in12k_ft_in1k",
+                Some(ModelLibrary::Timm),
+            ),
+            ("timm/resnet50.a1_in1k", Some(ModelLibrary::Timm)),
+            ("ViT-B-32", None),
+            ("a/b/c", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                ModelLibrary::infer_from_name(name),
+                expected,
+                "name {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_processing_time_as_duration() {
+        let response = TextEmbeddingResponse {
+            embeddings: vec![],
+            processing_time_ms: 7.2,
+        };
+        assert_eq!(response.processing_time(), Duration::from_micros(7200));
+
+        assert_eq!(millis_to_duration(0.5), Duration::from_micros(500));
+        assert_eq!(millis_to_duration(-1.0), Duration::ZERO);
+        assert_eq!(millis_to_duration(f32::NAN), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_normalize_embeddings() {
+        let mut response = ImageEmbeddingResponse {
+            embeddings: vec![vec![3.0, 4.0], vec![0.0, 0.0], vec![1.0, 2.0, 2.0]],
+            processing_time_ms: 1.0,
+        };
+
+        response.normalize_embeddings();
+
+        for row in [&response.embeddings[0], &response.embeddings[2]] {
+            assert!((crate::embeddings::l2_norm(row) - 1.0).abs() < 1e-6);
+        }
+        assert_eq!(response.embeddings[1], vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_dims_rejects_zero_and_serializes_as_number() {
+        assert_eq!(Dims::new(0), None);
+        let dims = Dims::new(256).unwrap();
+        assert_eq!(dims.get(), 256);
+        assert_eq!(serde_json::to_string(&dims).unwrap(), "256");
+
+        let request = TextEmbeddingRequest {
+            name: "m".to_string(),
+            text: vec!["a".to_string()],
+            normalize: None,
+            n_dims: Dims::new(64),
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["nDims"], serde_json::json!(64));
+
+        assert!(serde_json::from_str::<Dims>("0").is_err());
+    }
+
+    #[test]
+    fn test_malformed_float_row_reports_its_path() {
+        let deserializer = &mut serde_json::Deserializer::from_str(
+            r#"{"embeddings": [[0.1, 0.2], [0.3, "x"]], "processingTimeMs": 1.0}"#,
+        );
+        let err =
+            serde_path_to_error::deserialize::<_, TextEmbeddingResponse>(deserializer).unwrap_err();
+        assert_eq!(err.path().to_string(), "embeddings[1][1]");
+    }
+
+    #[test]
+    fn test_embeddings_decode_from_encoded_float32() {
+        let response: TextEmbeddingResponse = serde_json::from_str(
+            r#"{
+                "embeddings": {"dtype": "float32", "shape": [2, 2], "data": "AACAPwAAAMAAAAA/AACAPg=="},
+                "processingTimeMs": 1.0
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(response.embeddings, vec![vec![1.0, -2.0], vec![0.5, 0.25]]);
+
+        let response: EmbeddingResponse =
+            serde_json::from_str(r#"{"textEmbeddings": [[1.0, 2.0]], "processingTimeMs": 1.0}"#)
+                .unwrap();
+        assert_eq!(response.text_embeddings, Some(vec![vec![1.0, 2.0]]));
+        assert_eq!(response.image_embeddings, None);
+
+        let err = serde_json::from_str::<EmbeddingResponse>(
+            r#"{
+                "imageEmbeddings": {"dtype": "float32", "shape": [1, 3], "data": "AACAPwAAAMAAAAA/AACAPg=="},
+                "processingTimeMs": 1.0
+            }"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("shape [1, 3]"));
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_embeddings_decode_from_encoded_float16() {
+        let response: ImageEmbeddingResponse = serde_json::from_str(
+            r#"{
+                "embeddings": {"dtype": "float16", "shape": [2, 2], "data": "ADwAwAA4ADQ="},
+                "processingTimeMs": 1.0
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(response.embeddings, vec![vec![1.0, -2.0], vec![0.5, 0.25]]);
+    }
+}
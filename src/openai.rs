@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::IngrainClient;
+use crate::error::IngrainError;
+
+/// A response shaped like OpenAI's embeddings API, so code written against
+/// it keeps working; see [`IngrainClient::embeddings`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiEmbeddingResponse {
+    /// Always `list`.
+    pub object: String,
+    pub data: Vec<OpenAiEmbedding>,
+    pub model: String,
+    pub usage: OpenAiUsage,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiEmbedding {
+    /// Always `embedding`.
+    pub object: String,
+    pub embedding: Vec<f32>,
+    /// The position of the input this embedding belongs to.
+    pub index: usize,
+}
+
+/// The inference server does not report token counts, so both are zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenAiUsage {
+    pub prompt_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl IngrainClient {
+    /// Embeds `input` with [`IngrainClient::embed_text`] and reshapes the
+    /// result on the client into OpenAI's embeddings format.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn embeddings(
+        &self,
+        model: impl Into<String>,
+        input: Vec<String>,
+    ) -> Result<OpenAiEmbeddingResponse, IngrainError> {
+        let model = model.into();
+        let response = self.embed_text(model.clone(), input, None, None).await?;
+
+        Ok(OpenAiEmbeddingResponse {
+            object: "list".to_string(),
+            data: response
+                .embeddings
+                .into_iter()
+                .enumerate()
+                .map(|(index, embedding)| OpenAiEmbedding {
+                    object: "embedding".to_string(),
+                    embedding,
+                    index,
+                })
+                .collect(),
+            model,
+            usage: OpenAiUsage::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::Method::POST;
+    use httpmock::MockServer;
+
+    #[tokio::test]
+    async fn test_embeddings_are_indexed_in_input_order() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/embed_text")
+                .body_includes(r#""text":["first","second"]"#);
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(r#"{"embeddings": [[0.1, 0.2], [0.3, 0.4]], "processingTimeMs": 1.0}"#);
+        });
+
+        let client = IngrainClient::new("http://localhost:8687", &server.url(""));
+
+        let response = client
+            .embeddings("test-model", vec!["first".into(), "second".into()])
+            .await
+            .unwrap();
+
+        assert_eq!(response.object, "list");
+        assert_eq!(response.model, "test-model");
+        assert_eq!(response.data.len(), 2);
+        for (index, (data, expected)) in response
+            .data
+            .iter()
+            .zip([vec![0.1, 0.2], vec![0.3, 0.4]])
+            .enumerate()
+        {
+            assert_eq!(data.object, "embedding");
+            assert_eq!(data.index, index);
+            assert_eq!(data.embedding, expected);
+        }
+        mock.assert();
+    }
+}
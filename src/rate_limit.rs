@@ -0,0 +1,60 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::{Instant, sleep_until};
+
+/// The longest wait between two requests, which also keeps `Instant`
+/// arithmetic from overflowing for tiny rates.
+const MAX_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A token bucket holding a single token that refills `requests_per_second`
+/// times a second, so requests are spaced evenly instead of sent in bursts.
+/// Shared by a client and its clones.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    /// When the next token becomes available.
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Non-positive or non-finite rates are treated as one request per
+    /// second, and rates below one request a day as one a day.
+    pub(crate) fn new(requests_per_second: f64) -> Self {
+        let rate = if requests_per_second.is_finite() && requests_per_second > 0.0 {
+            requests_per_second
+        } else {
+            1.0
+        };
+        RateLimiter {
+            interval: Duration::try_from_secs_f64(1.0 / rate)
+                .map_or(MAX_INTERVAL, |interval| interval.min(MAX_INTERVAL)),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until a token is available and takes it. Waiters are served in
+    /// the order they called this.
+    pub(crate) async fn acquire(&self) {
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_rates_fall_back_to_one_per_second() {
+        for rate in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(RateLimiter::new(rate).interval, Duration::from_secs(1));
+        }
+        assert_eq!(RateLimiter::new(1e-20).interval, MAX_INTERVAL);
+    }
+}
@@ -1,56 +1,791 @@
-use std::error::Error;
-use std::time::Duration;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
-use reqwest::RequestBuilder;
+use reqwest::header::{ETAG, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 
-pub async fn retry<T>(
+use crate::error::IngrainError;
+use crate::models::ApiErrorBody;
+use crate::rate_limit::RateLimiter;
+use crate::telemetry::Telemetry;
+
+/// Controls how the delay between retry attempts grows.
+///
+/// The delay before retry `n` (starting at 0) is
+/// `retry_delay_ms * multiplier^n`, capped at `max_delay_ms`, plus a random
+/// jitter of up to `jitter_ms`. A `multiplier` of `0.0` or `1.0` with no
+/// jitter keeps the delay flat.
+///
+/// A `Retry-After` header on a 429 or 503 response replaces that delay,
+/// capped at `max_delay_ms`, or at [`MAX_RETRY_AFTER`] when it is not set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffConfig {
+    pub multiplier: f64,
+    pub max_delay_ms: Option<u64>,
+    pub jitter_ms: u64,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        BackoffConfig::flat()
+    }
+}
+
+impl BackoffConfig {
+    pub fn flat() -> Self {
+        BackoffConfig {
+            multiplier: 1.0,
+            max_delay_ms: None,
+            jitter_ms: 0,
+        }
+    }
+
+    pub fn exponential(max_delay_ms: u64, jitter_ms: u64) -> Self {
+        BackoffConfig {
+            multiplier: 2.0,
+            max_delay_ms: Some(max_delay_ms),
+            jitter_ms,
+        }
+    }
+
+    /// `jitter_sample` is expected to be in `[0, 1)`.
+    fn delay(&self, base_delay_ms: u64, attempt: u16, jitter_sample: f64) -> Duration {
+        let multiplier = if self.multiplier > 1.0 {
+            self.multiplier
+        } else {
+            1.0
+        };
+
+        let mut delay_ms = base_delay_ms as f64 * multiplier.powi(attempt as i32);
+        if let Some(max_delay_ms) = self.max_delay_ms {
+            delay_ms = delay_ms.min(max_delay_ms as f64);
+        }
+        delay_ms += self.jitter_ms as f64 * jitter_sample;
+
+        Duration::from_millis(delay_ms as u64)
+    }
+}
+
+fn jitter_sample() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// The default retry predicate: request timeouts, rate limiting and the
+/// usual gateway/server errors are retried, other statuses fail immediately.
+pub fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Called before every retry with the number of the attempt that failed,
+/// starting at 1, and its error.
+#[derive(Clone)]
+pub(crate) struct RetryHook(pub(crate) Arc<RetryHookFn>);
+
+type RetryHookFn = dyn Fn(u16, &IngrainError) + Send + Sync;
+
+impl fmt::Debug for RetryHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RetryHook")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub retries: u16,
+    pub retry_delay_ms: u64,
+    pub backoff: BackoffConfig,
+    pub retry_on: fn(StatusCode) -> bool,
+    /// Total time budget across all attempts and delays, if any.
+    pub deadline: Option<Duration>,
+    pub(crate) on_retry: Option<RetryHook>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            retries: 0,
+            retry_delay_ms: 0,
+            backoff: BackoffConfig::default(),
+            retry_on: is_transient_status,
+            deadline: None,
+            on_retry: None,
+        }
+    }
+}
+
+/// What the retry loop went through for one call. `errors` holds the error
+/// of every attempt that was retried; the error of a final failed attempt
+/// is the call's own error instead.
+#[derive(Debug, Default)]
+pub struct RetryReport {
+    pub attempts: u16,
+    pub errors: Vec<IngrainError>,
+}
+
+/// The status and selected headers of a successful response; see
+/// [`IngrainClient::embed_text_with_meta`](crate::IngrainClient::embed_text_with_meta).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    pub status: StatusCode,
+    pub etag: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl ResponseMeta {
+    fn from_response(response: &Response) -> ResponseMeta {
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        ResponseMeta {
+            status: response.status(),
+            etag: header(ETAG.as_str()),
+            request_id: header("x-request-id"),
+        }
+    }
+}
+
+/// Everything about one call that [`retry`] needs besides the request and
+/// the retry settings.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CallContext<'a> {
+    /// Stops the call with [`IngrainError::Cancelled`] once cancelled.
+    pub cancel: Option<&'a CancellationToken>,
+    /// Every attempt holds one permit while in flight.
+    pub permits: Option<&'a Semaphore>,
+    /// Every attempt waits for a token before it is sent.
+    pub rate_limiter: Option<&'a RateLimiter>,
+    /// Stops the call with [`IngrainError::DeadlineExceeded`] once reached.
+    pub deadline: Option<Instant>,
+    /// Receives every attempt, and the error of every retried attempt.
+    pub report: Option<&'a Mutex<RetryReport>>,
+    /// Receives the status and headers of the successful response.
+    pub meta: Option<&'a Mutex<Option<ResponseMeta>>>,
+    /// Passed on to [`parse_response`].
+    pub strict: bool,
+    /// Passed on to [`parse_response`].
+    pub telemetry: Option<Telemetry<'a>>,
+}
+
+/// Retry settings for individual calls; see
+/// [`IngrainClient::with_retry_override`](crate::IngrainClient::with_retry_override).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryOverride {
+    pub retries: u16,
+    pub delay_ms: u64,
+}
+
+impl RetryConfig {
+    pub(crate) fn with_override(&self, retry_override: RetryOverride) -> RetryConfig {
+        RetryConfig {
+            retries: retry_override.retries,
+            retry_delay_ms: retry_override.delay_ms,
+            ..self.clone()
+        }
+    }
+
+    fn should_retry(&self, err: &IngrainError) -> bool {
+        match err {
+            IngrainError::Http { status, .. } => (self.retry_on)(*status),
+            IngrainError::Network(_) | IngrainError::Connect(_) | IngrainError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+
+    fn past_deadline(&self, elapsed: Duration) -> bool {
+        self.deadline.is_some_and(|deadline| elapsed > deadline)
+    }
+
+    /// A delay the server asked for with `Retry-After`, capped at
+    /// `max_delay_ms`, or at [`MAX_RETRY_AFTER`] without one, so a bogus
+    /// value cannot park the call for hours.
+    fn server_delay(&self, delay: Duration) -> Duration {
+        let cap = self
+            .backoff
+            .max_delay_ms
+            .map_or(MAX_RETRY_AFTER, Duration::from_millis);
+        delay.min(cap)
+    }
+
+    fn delay(&self, attempt: u16) -> Duration {
+        let sample = if self.backoff.jitter_ms > 0 {
+            jitter_sample()
+        } else {
+            0.0
+        };
+        self.backoff.delay(self.retry_delay_ms, attempt, sample)
+    }
+}
+
+/// The longest `Retry-After` delay honoured when
+/// [`BackoffConfig::max_delay_ms`] is not set.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Reads the `Retry-After` header on 429 and 503 responses, accepting both
+/// the delay-seconds and HTTP-date forms.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let status = response.status();
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, SystemTime::now())
+}
+
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// With `strict`, a success body that has fields `T` does not know fails
+/// with [`IngrainError::UnexpectedField`] instead of being accepted. With
+/// `telemetry`, the processing time of a parsed body is recorded.
+pub(crate) async fn parse_response<T>(
+    response: Response,
+    strict: bool,
+    telemetry: Option<Telemetry<'_>>,
+) -> Result<T, IngrainError>
+where
+    T: DeserializeOwned,
+{
+    let status = response.status();
+    let body = response.text().await?;
+
+    if !status.is_success() {
+        let detail = serde_json::from_str::<ApiErrorBody>(&body)
+            .ok()
+            .and_then(|error| error.summary());
+        return Err(IngrainError::Http {
+            status,
+            body,
+            detail,
+        });
+    }
+
+    let mut unexpected = None;
+    let deserializer = &mut serde_json::Deserializer::from_str(&body);
+    let parsed = if strict {
+        let mut record = |path: serde_ignored::Path| {
+            unexpected.get_or_insert_with(|| path.to_string());
+        };
+        serde_path_to_error::deserialize(serde_ignored::Deserializer::new(
+            deserializer,
+            &mut record,
+        ))
+    } else {
+        serde_path_to_error::deserialize(deserializer)
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return Err(IngrainError::Deserialize {
+                path: e.path().to_string(),
+                source: e.into_inner(),
+                body,
+            });
+        }
+    };
+    match unexpected {
+        Some(path) => Err(IngrainError::UnexpectedField { path, body }),
+        None => {
+            if let Some(telemetry) = telemetry {
+                telemetry.record(status, &body);
+            }
+            Ok(parsed)
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn status_code(err: &IngrainError) -> Option<u16> {
+    match err {
+        IngrainError::Http { status, .. } => Some(status.as_u16()),
+        _ => None,
+    }
+}
+
+/// Resolves once `deadline` is reached, or never when there is none.
+pub(crate) async fn deadline_reached(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Resolves once `token` is cancelled, or never when there is no token.
+pub async fn cancelled(token: Option<&CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn attempt<T>(
+    request: RequestBuilder,
+    context: &CallContext<'_>,
+) -> (Result<T, IngrainError>, Option<Duration>)
+where
+    T: DeserializeOwned,
+{
+    match request.send().await {
+        Ok(response) => {
+            #[cfg(feature = "log")]
+            log::debug!("{} responded with {}", response.url(), response.status());
+            let server_delay = retry_after(&response);
+            let meta = context.meta.map(|_| ResponseMeta::from_response(&response));
+            let result = parse_response(response, context.strict, context.telemetry).await;
+            if let (Some(slot), Some(meta), Ok(_)) = (context.meta, meta, &result) {
+                *slot.lock().unwrap() = Some(meta);
+            }
+            (result, server_delay)
+        }
+        Err(e) => (Err(e.into()), None),
+    }
+}
+
+/// Runs `future` once `rate_limiter` has handed out a token and while
+/// holding one of `permits`.
+pub(crate) async fn limited<F: Future>(
+    permits: Option<&Semaphore>,
+    rate_limiter: Option<&RateLimiter>,
+    future: F,
+) -> F::Output {
+    if let Some(rate_limiter) = rate_limiter {
+        rate_limiter.acquire().await;
+    }
+    let _permit = match permits {
+        Some(permits) => Some(
+            permits
+                .acquire()
+                .await
+                .expect("the client never closes its semaphore"),
+        ),
+        None => None,
+    };
+    future.await
+}
+
+/// Sends the request until it succeeds, fails with a non-retryable error or
+/// runs out of attempts. Stops with [`IngrainError::Cancelled`] as soon as
+/// the context's `cancel` fires, whether mid-request or while waiting between
+/// attempts. A request whose body cannot be cloned, such as a stream, fails
+/// with [`IngrainError::NonRetryableRequest`] up front when retries are
+/// configured, and is sent once otherwise. Once the context's `deadline`
+/// is reached, the call stops with [`IngrainError::DeadlineExceeded`]
+/// wherever it is. When `permits` is set, every attempt holds one of its permits
+/// while it is in flight, but not while waiting to retry. With a `deadline`
+/// configured, no further attempt is started, and no delay begun, that would
+/// end past it.
+pub(crate) async fn retry<T>(
     request_builder: RequestBuilder,
-    retries: u16,
-    retry_delay_ms: u64,
-) -> Result<T, Box<dyn Error>>
+    config: &RetryConfig,
+    context: &CallContext<'_>,
+) -> Result<T, IngrainError>
 where
     T: DeserializeOwned + Send + 'static,
 {
-    let mut last_err: Option<String> = None;
-
-    for attempt in 0..retries + 1 {
-        let request = request_builder
-            .try_clone()
-            .ok_or("Failed to clone request")?;
-
-        match request.send().await {
-            Ok(response) => {
-                let status = response.status();
-                let body = response.text().await?;
-
-                if status.is_success() {
-                    match serde_json::from_str::<T>(&body) {
-                        Ok(parsed) => return Ok(parsed),
-                        Err(e) => {
-                            last_err =
-                                Some(format!("Failed to parse response: {} (body: {})", e, body));
-                        }
-                    }
-                } else {
-                    last_err = Some(format!(
-                        "Request failed with status: {} (body: {})",
-                        status, body
-                    ));
-                }
-            }
-            Err(e) => {
-                last_err = Some(format!("Network error: {}", e));
+    let CallContext {
+        cancel,
+        permits,
+        rate_limiter,
+        deadline,
+        report,
+        ..
+    } = *context;
+    let retries = config.retries;
+    // A streaming body can only be sent once, so fail before the first
+    // attempt rather than after it.
+    if retries > 0 && request_builder.try_clone().is_none() {
+        return Err(IngrainError::NonRetryableRequest);
+    }
+    let mut request_builder = Some(request_builder);
+    let mut attempts: u16 = 0;
+    let started = Instant::now();
+
+    // Counts up to `retries` inclusive; every path out of the loop returns,
+    // so `retries == u16::MAX` neither overflows nor runs out of attempts.
+    let mut attempt_number: u16 = 0;
+    loop {
+        let request = match request_builder.as_ref().and_then(RequestBuilder::try_clone) {
+            Some(request) => request,
+            // Only reached on the sole attempt of a call without retries.
+            None => request_builder
+                .take()
+                .ok_or(IngrainError::NonRetryableRequest)?,
+        };
+
+        let (result, server_delay) = tokio::select! {
+            biased;
+            _ = cancelled(cancel) => return Err(IngrainError::Cancelled),
+            _ = deadline_reached(deadline) => return Err(IngrainError::DeadlineExceeded),
+            outcome = limited(permits, rate_limiter, attempt::<T>(request, context)) => outcome,
+        };
+        attempts = attempts.saturating_add(1);
+        if let Some(report) = report {
+            let mut report = report.lock().unwrap();
+            report.attempts = report.attempts.saturating_add(1);
+        }
+
+        let err = match result {
+            Ok(parsed) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    attempt = attempts,
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "request succeeded"
+                );
+                return Ok(parsed);
             }
+            Err(e) => e,
+        };
+
+        if !config.should_retry(&err) {
+            #[cfg(feature = "tracing")]
+            tracing::error!(
+                attempt = attempts,
+                status = status_code(&err),
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                error = %err,
+                "request failed"
+            );
+            #[cfg(feature = "log")]
+            log::warn!("request failed on attempt {}: {}", attempts, err);
+            return Err(err);
+        }
+
+        if attempt_number == retries {
+            return Err(exhausted(err, attempts, retries, started));
+        }
+        let delay = match server_delay {
+            Some(delay) => config.server_delay(delay),
+            None => config.delay(attempt_number),
+        };
+        if config.past_deadline(started.elapsed() + delay) {
+            return Err(exhausted(err, attempts, retries, started));
+        }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            attempt = attempts,
+            status = status_code(&err),
+            delay_ms = delay.as_millis() as u64,
+            error = %err,
+            "request failed, retrying"
+        );
+        #[cfg(feature = "log")]
+        log::warn!(
+            "request failed on attempt {}, retrying in {}ms: {}",
+            attempts,
+            delay.as_millis(),
+            err
+        );
+        if let Some(hook) = &config.on_retry {
+            (hook.0)(attempts, &err);
+        }
+        tokio::select! {
+            biased;
+            _ = cancelled(cancel) => return Err(IngrainError::Cancelled),
+            _ = deadline_reached(deadline) => return Err(IngrainError::DeadlineExceeded),
+            _ = sleep(delay) => {}
+        }
+
+        attempt_number += 1;
+        if config.past_deadline(started.elapsed()) {
+            return Err(exhausted(err, attempts, retries, started));
+        }
+        if let Some(report) = report {
+            report.lock().unwrap().errors.push(err);
+        }
+    }
+}
+
+/// The error a call ends with once it has run out of attempts or time.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn exhausted(last: IngrainError, attempts: u16, retries: u16, started: Instant) -> IngrainError {
+    #[cfg(feature = "tracing")]
+    tracing::error!(
+        attempts,
+        status = status_code(&last),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        error = %last,
+        "request failed"
+    );
+    #[cfg(feature = "log")]
+    log::warn!("request failed after {} attempts: {}", attempts, last);
+    if retries == 0 {
+        last
+    } else {
+        IngrainError::RetriesExhausted {
+            attempts,
+            last: Box::new(last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delays(backoff: BackoffConfig, base_delay_ms: u64, attempts: u16, sample: f64) -> Vec<u64> {
+        (0..attempts)
+            .map(|attempt| backoff.delay(base_delay_ms, attempt, sample).as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_flat_backoff_matches_fixed_delay() {
+        assert_eq!(
+            delays(BackoffConfig::flat(), 100, 4, 0.5),
+            vec![100, 100, 100, 100]
+        );
+    }
+
+    #[test]
+    fn test_zero_multiplier_is_flat() {
+        let backoff = BackoffConfig {
+            multiplier: 0.0,
+            max_delay_ms: None,
+            jitter_ms: 0,
+        };
+        assert_eq!(delays(backoff, 50, 3, 0.0), vec![50, 50, 50]);
+    }
+
+    #[test]
+    fn test_exponential_backoff_doubles_until_capped() {
+        let backoff = BackoffConfig::exponential(1000, 0);
+        assert_eq!(
+            delays(backoff, 100, 6, 0.0),
+            vec![100, 200, 400, 800, 1000, 1000]
+        );
+    }
+
+    #[test]
+    fn test_jitter_is_added_within_bound() {
+        let backoff = BackoffConfig::exponential(1000, 50);
+        assert_eq!(delays(backoff, 100, 3, 0.0), vec![100, 200, 400]);
+        assert_eq!(delays(backoff, 100, 3, 0.5), vec![125, 225, 425]);
+
+        let config = RetryConfig {
+            retries: 3,
+            retry_delay_ms: 100,
+            backoff,
+            ..RetryConfig::default()
+        };
+        for attempt in 0..3 {
+            let delay = config.delay(attempt).as_millis() as u64;
+            let base = 100 * 2u64.pow(attempt as u32);
+            assert!(delay >= base && delay <= base + 50);
+        }
+    }
+
+    #[test]
+    fn test_transient_statuses() {
+        for status in [408, 429, 500, 502, 503, 504] {
+            assert!(is_transient_status(StatusCode::from_u16(status).unwrap()));
+        }
+        for status in [400, 401, 403, 404, 422] {
+            assert!(!is_transient_status(StatusCode::from_u16(status).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let now = SystemTime::now();
+        assert_eq!(parse_retry_after("2", now), Some(Duration::from_secs(2)));
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = httpdate::parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:05 GMT", now),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_huge_retry_after_is_capped() {
+        let day = parse_retry_after("86400", SystemTime::now()).unwrap();
+
+        let config = RetryConfig::default();
+        assert_eq!(config.server_delay(day), MAX_RETRY_AFTER);
+        assert_eq!(
+            config.server_delay(Duration::from_secs(2)),
+            Duration::from_secs(2)
+        );
+
+        let config = RetryConfig {
+            backoff: BackoffConfig::exponential(500, 0),
+            ..RetryConfig::default()
+        };
+        assert_eq!(config.server_delay(day), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_parse_retry_after_invalid() {
+        assert_eq!(parse_retry_after("soon", SystemTime::now()), None);
+    }
+
+    fn streaming_request(url: &str) -> RequestBuilder {
+        let chunks = futures::stream::iter([Ok::<_, std::io::Error>("{}")]);
+        reqwest::Client::new()
+            .post(url)
+            .body(reqwest::Body::wrap_stream(chunks))
+    }
+
+    #[tokio::test]
+    async fn test_max_retries_stop_at_deadline() {
+        let server = httpmock::MockServer::start();
+        server.mock(|when, then| {
+            when.any_request();
+            then.status(503);
+        });
+
+        let config = RetryConfig {
+            retries: u16::MAX,
+            retry_delay_ms: 20,
+            deadline: Some(Duration::from_millis(100)),
+            ..RetryConfig::default()
+        };
+        let result = retry::<serde_json::Value>(
+            reqwest::Client::new().get(server.url("/health")),
+            &config,
+            &CallContext::default(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(IngrainError::RetriesExhausted { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_body_with_retries_fails_up_front() {
+        let server = httpmock::MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.any_request();
+            then.status(200).body("{}");
+        });
+
+        let config = RetryConfig {
+            retries: 2,
+            ..RetryConfig::default()
+        };
+        let result = retry::<serde_json::Value>(
+            streaming_request(&server.url("/embed")),
+            &config,
+            &CallContext::default(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(IngrainError::NonRetryableRequest)));
+        mock.assert_calls(0);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_body_without_retries_is_sent_once() {
+        let server = httpmock::MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.any_request();
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body("{}");
+        });
+
+        let result = retry::<serde_json::Value>(
+            streaming_request(&server.url("/embed")),
+            &RetryConfig::default(),
+            &CallContext::default(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), serde_json::json!({}));
+        mock.assert_calls(1);
+    }
+
+    #[cfg(feature = "log")]
+    struct CapturingLogger;
+
+    #[cfg(feature = "log")]
+    static LOGGED: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+    #[cfg(feature = "log")]
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _metadata: &log::Metadata<'_>) -> bool {
+            true
         }
 
-        if attempt < retries {
-            sleep(Duration::from_millis(retry_delay_ms)).await;
+        fn log(&self, record: &log::Record<'_>) {
+            LOGGED
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
         }
+
+        fn flush(&self) {}
     }
 
-    Err(last_err
-        .unwrap_or_else(|| "Unknown error".to_string())
-        .into())
+    #[cfg(feature = "log")]
+    #[tokio::test]
+    async fn test_retried_failure_is_logged_as_warning() {
+        static LOGGER: CapturingLogger = CapturingLogger;
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let server = httpmock::MockServer::start();
+        server.mock(|when, then| {
+            when.any_request();
+            then.status(503).body("busy");
+        });
+
+        let config = RetryConfig {
+            retries: 1,
+            ..RetryConfig::default()
+        };
+        let url = server.url("/embed_text");
+        let result = retry::<serde_json::Value>(
+            reqwest::Client::new().post(&url),
+            &config,
+            &CallContext::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(IngrainError::RetriesExhausted { .. })));
+
+        let logged = LOGGED.lock().unwrap();
+        assert!(logged.iter().any(|(level, message)| {
+            *level == log::Level::Debug
+                && message == &format!("{} responded with 503 Service Unavailable", url)
+        }));
+        assert!(logged.iter().any(|(level, message)| {
+            *level == log::Level::Warn
+                && message.starts_with("request failed on attempt 1, retrying")
+        }));
+    }
 }
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// Receives the server-side processing time of every successful inference
+/// call, e.g. for cost attribution; see
+/// [`IngrainClientBuilder::telemetry`](crate::IngrainClientBuilder::telemetry).
+/// `method` is the endpoint, such as `embed_text`. Called on the task that
+/// made the call, so implementations should be quick.
+pub trait TelemetrySink: Send + Sync {
+    fn record(&self, method: &str, processing_time_ms: f32, status: StatusCode);
+}
+
+/// A [`TelemetrySink`] that totals processing time and counts calls per
+/// endpoint.
+#[derive(Debug, Default)]
+pub struct ProcessingTimeTotals {
+    totals: Mutex<HashMap<String, (u64, f64)>>,
+}
+
+impl ProcessingTimeTotals {
+    /// The summed processing time of all calls to `method`.
+    pub fn total_ms(&self, method: &str) -> f64 {
+        self.totals
+            .lock()
+            .unwrap()
+            .get(method)
+            .map_or(0.0, |(_, total)| *total)
+    }
+
+    /// The number of calls to `method` recorded.
+    pub fn calls(&self, method: &str) -> u64 {
+        self.totals
+            .lock()
+            .unwrap()
+            .get(method)
+            .map_or(0, |(calls, _)| *calls)
+    }
+
+    /// The summed processing time of all calls to any endpoint.
+    pub fn overall_ms(&self) -> f64 {
+        self.totals
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(_, total)| total)
+            .sum()
+    }
+}
+
+impl TelemetrySink for ProcessingTimeTotals {
+    fn record(&self, method: &str, processing_time_ms: f32, _status: StatusCode) {
+        let mut totals = self.totals.lock().unwrap();
+        let (calls, total) = totals.entry(method.to_string()).or_default();
+        *calls += 1;
+        *total += f64::from(processing_time_ms);
+    }
+}
+
+/// Where the retry loop reports processing times for one call.
+#[derive(Clone, Copy)]
+pub(crate) struct Telemetry<'a> {
+    pub sink: &'a dyn TelemetrySink,
+    pub method: &'a str,
+}
+
+impl fmt::Debug for Telemetry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Telemetry")
+            .field("method", &self.method)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Telemetry<'_> {
+    /// Reads `processingTimeMs` from a success body, which parses it a second
+    /// time, and records it. Bodies without the field are not recorded.
+    pub(crate) fn record(&self, status: StatusCode, body: &str) {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ProcessingTime {
+            processing_time_ms: f32,
+        }
+
+        if let Ok(time) = serde_json::from_str::<ProcessingTime>(body) {
+            self.sink
+                .record(self.method, time.processing_time_ms, status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_are_kept_per_method() {
+        let totals = ProcessingTimeTotals::default();
+        totals.record("embed_text", 1.5, StatusCode::OK);
+        totals.record("embed_text", 2.5, StatusCode::OK);
+        totals.record("embed_image", 4.0, StatusCode::OK);
+
+        assert_eq!(totals.total_ms("embed_text"), 4.0);
+        assert_eq!(totals.calls("embed_text"), 2);
+        assert_eq!(totals.calls("classify_image"), 0);
+        assert_eq!(totals.overall_ms(), 8.0);
+    }
+}
@@ -1,6 +1,5 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use tokio;
 use tokio::sync::Mutex;
 
 use ingrain_rs::IngrainClient;
@@ -45,7 +44,7 @@ async fn test_model_server_health() {
 
     assert!(res.is_ok());
     let resp = res.unwrap();
-    assert!(resp.message != "".to_string());
+    assert!(!resp.message.is_empty());
 }
 
 #[tokio::test]
@@ -56,7 +55,7 @@ async fn test_inference_server_health() {
 
     assert!(res.is_ok());
     let resp = res.unwrap();
-    assert!(resp.message != "".to_string());
+    assert!(!resp.message.is_empty());
 }
 
 #[tokio::test]
@@ -64,13 +63,13 @@ async fn test_load_openclip_model() {
     with_model_lock(OPENCLIP_MODEL, || async {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
         let res = client
-            .load_model(OPENCLIP_MODEL.to_string(), ModelLibrary::OpenClip)
+            .load_model(OPENCLIP_MODEL, ModelLibrary::OpenClip)
             .await;
         assert!(res.is_ok());
         let resp = res.unwrap();
         assert!(resp.message.contains("loaded"));
 
-        let _ = client.unload_model(OPENCLIP_MODEL.to_string()).await;
+        let _ = client.unload_model(OPENCLIP_MODEL).await;
     })
     .await;
 }
@@ -81,7 +80,7 @@ async fn test_load_sentence_transformers_model() {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
         let res = client
             .load_model(
-                SENTENCE_TRANSFORMER_MODEL.to_string(),
+                SENTENCE_TRANSFORMER_MODEL,
                 ModelLibrary::SentenceTransformers,
             )
             .await;
@@ -89,9 +88,7 @@ async fn test_load_sentence_transformers_model() {
         let resp = res.unwrap();
         assert!(resp.message.contains("loaded"));
 
-        let _ = client
-            .unload_model(SENTENCE_TRANSFORMER_MODEL.to_string())
-            .await;
+        let _ = client.unload_model(SENTENCE_TRANSFORMER_MODEL).await;
     })
     .await;
 }
@@ -100,14 +97,12 @@ async fn test_load_sentence_transformers_model() {
 async fn test_load_timm_model() {
     with_model_lock(TIMM_MODEL, || async {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
-        let res = client
-            .load_model(TIMM_MODEL.to_string(), ModelLibrary::Timm)
-            .await;
+        let res = client.load_model(TIMM_MODEL, ModelLibrary::Timm).await;
         assert!(res.is_ok());
         let resp = res.unwrap();
         assert!(resp.message.contains("loaded"));
 
-        let _ = client.unload_model(TIMM_MODEL.to_string()).await;
+        let _ = client.unload_model(TIMM_MODEL).await;
     })
     .await;
 }
@@ -118,20 +113,15 @@ async fn test_embed_text() {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
         let res = client
             .load_model(
-                SENTENCE_TRANSFORMER_MODEL.to_string(),
+                SENTENCE_TRANSFORMER_MODEL,
                 ModelLibrary::SentenceTransformers,
             )
             .await;
         assert!(res.is_ok());
 
-        let test_text = vec!["This is a sentence.".to_string()];
+        let test_text = vec!["This is a sentence."];
         let res = client
-            .embed_text(
-                SENTENCE_TRANSFORMER_MODEL.to_string(),
-                test_text,
-                None,
-                None,
-            )
+            .embed_text(SENTENCE_TRANSFORMER_MODEL, test_text, None, None)
             .await;
 
         assert!(res.is_ok());
@@ -140,9 +130,7 @@ async fn test_embed_text() {
 
         assert_eq!(resp.embeddings[0].len(), 384);
 
-        let _ = client
-            .unload_model(SENTENCE_TRANSFORMER_MODEL.to_string())
-            .await;
+        let _ = client.unload_model(SENTENCE_TRANSFORMER_MODEL).await;
     })
     .await;
 }
@@ -151,11 +139,11 @@ async fn test_embed_text() {
 async fn test_embed_image() {
     with_model_lock(OPENCLIP_MODEL, || async {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
-        let res = client.load_model(OPENCLIP_MODEL.to_string(), ModelLibrary::OpenClip).await;
+        let res = client.load_model(OPENCLIP_MODEL, ModelLibrary::OpenClip).await;
         assert!(res.is_ok());
-        let test_image = vec!["data:image/jpeg;base64,iVBORw0KGgoAAAANSUhEUgAAAOAAAADgCAIAAACVT/22AAACkElEQVR4nOzUMQ0CYRgEUQ5wgwAUnA+EUKKJBkeowAHVJd/kz3sKtpjs9fH9nDjO/npOT1jKeXoA/CNQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKRtl/t7esNSbvs2PWEpHpQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIl7RcAAP//iL8GbQ2nM1wAAAAASUVORK5CYII=".to_string()];
+        let test_image = vec!["data:image/jpeg;base64,iVBORw0KGgoAAAANSUhEUgAAAOAAAADgCAIAAACVT/22AAACkElEQVR4nOzUMQ0CYRgEUQ5wgwAUnA+EUKKJBkeowAHVJd/kz3sKtpjs9fH9nDjO/npOT1jKeXoA/CNQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKRtl/t7esNSbvs2PWEpHpQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIl7RcAAP//iL8GbQ2nM1wAAAAASUVORK5CYII="];
         let res = client.embed_image(
-            OPENCLIP_MODEL.to_string(),
+            OPENCLIP_MODEL,
             test_image,
             None,
             None,
@@ -167,7 +155,7 @@ async fn test_embed_image() {
         let resp = res.unwrap();
         assert_eq!(resp.embeddings[0].len(), 512);
 
-        let _ = client.unload_model(OPENCLIP_MODEL.to_string()).await;
+        let _ = client.unload_model(OPENCLIP_MODEL).await;
     }).await;
 }
 
@@ -175,11 +163,11 @@ async fn test_embed_image() {
 async fn test_classify_image() {
     with_model_lock(TIMM_MODEL, || async {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
-        let res = client.load_model(TIMM_MODEL.to_string(), ModelLibrary::Timm).await;
+        let res = client.load_model(TIMM_MODEL, ModelLibrary::Timm).await;
         assert!(res.is_ok());
-        let test_image = vec!["data:image/jpeg;base64,iVBORw0KGgoAAAANSUhEUgAAAOAAAADgCAIAAACVT/22AAACkElEQVR4nOzUMQ0CYRgEUQ5wgwAUnA+EUKKJBkeowAHVJd/kz3sKtpjs9fH9nDjO/npOT1jKeXoA/CNQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKRtl/t7esNSbvs2PWEpHpQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIl7RcAAP//iL8GbQ2nM1wAAAAASUVORK5CYII=".to_string()];
+        let test_image = vec!["data:image/jpeg;base64,iVBORw0KGgoAAAANSUhEUgAAAOAAAADgCAIAAACVT/22AAACkElEQVR4nOzUMQ0CYRgEUQ5wgwAUnA+EUKKJBkeowAHVJd/kz3sKtpjs9fH9nDjO/npOT1jKeXoA/CNQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKRtl/t7esNSbvs2PWEpHpQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIl7RcAAP//iL8GbQ2nM1wAAAAASUVORK5CYII="];
         let res = client.classify_image(
-            TIMM_MODEL.to_string(),
+            TIMM_MODEL,
             test_image,
             None,
         ).await;
@@ -189,7 +177,7 @@ async fn test_classify_image() {
         let resp = res.unwrap();
         assert_eq!(resp.probabilities[0].len(), 1000);
 
-        let _ = client.unload_model(TIMM_MODEL.to_string()).await;
+        let _ = client.unload_model(TIMM_MODEL).await;
     }).await;
 }
 
@@ -197,12 +185,12 @@ async fn test_classify_image() {
 async fn test_embed_text_image() {
     with_model_lock(OPENCLIP_MODEL, || async {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
-        let res = client.load_model(OPENCLIP_MODEL.to_string(), ModelLibrary::OpenClip).await;
+        let res = client.load_model(OPENCLIP_MODEL, ModelLibrary::OpenClip).await;
         assert!(res.is_ok());
         let test_image = vec!["data:image/jpeg;base64,iVBORw0KGgoAAAANSUhEUgAAAOAAAADgCAIAAACVT/22AAACkElEQVR4nOzUMQ0CYRgEUQ5wgwAUnA+EUKKJBkeowAHVJd/kz3sKtpjs9fH9nDjO/npOT1jKeXoA/CNQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKQJlDSBkiZQ0gRKmkBJEyhpAiVNoKRtl/t7esNSbvs2PWEpHpQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIlTaCkCZQ0gZImUNIESppASRMoaQIl7RcAAP//iL8GbQ2nM1wAAAAASUVORK5CYII=".to_string()];
         let test_text = vec!["Text1".to_string(), "text 2".to_string()];
         let res = client.embed(
-            OPENCLIP_MODEL.to_string(),
+            OPENCLIP_MODEL,
             Some(test_text),
             Some(test_image),
             None,
@@ -220,7 +208,7 @@ async fn test_embed_text_image() {
         assert_eq!(te[0].len(), 512);
         assert_eq!(resp.image_embeddings.unwrap()[0].len(), 512);
 
-        let _ = client.unload_model(OPENCLIP_MODEL.to_string()).await;
+        let _ = client.unload_model(OPENCLIP_MODEL).await;
     }).await;
 }
 
@@ -230,21 +218,19 @@ async fn test_model_embed_dims() {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
         let res = client
             .load_model(
-                SENTENCE_TRANSFORMER_MODEL.to_string(),
+                SENTENCE_TRANSFORMER_MODEL,
                 ModelLibrary::SentenceTransformers,
             )
             .await;
         assert!(res.is_ok());
 
         let res = client
-            .model_embedding_size(SENTENCE_TRANSFORMER_MODEL.to_string())
+            .model_embedding_size(SENTENCE_TRANSFORMER_MODEL)
             .await;
         assert!(res.is_ok());
         let resp = res.unwrap();
         assert_eq!(resp.embedding_size, 384);
-        let _ = client
-            .unload_model(SENTENCE_TRANSFORMER_MODEL.to_string())
-            .await;
+        let _ = client.unload_model(SENTENCE_TRANSFORMER_MODEL).await;
     })
     .await;
 }
@@ -253,18 +239,14 @@ async fn test_model_embed_dims() {
 async fn test_model_labels() {
     with_model_lock(TIMM_MODEL, || async {
         let client = IngrainClient::new(MODEL_BASE_URL, INFERENCE_BASE_URL);
-        let res = client
-            .load_model(TIMM_MODEL.to_string(), ModelLibrary::Timm)
-            .await;
+        let res = client.load_model(TIMM_MODEL, ModelLibrary::Timm).await;
         assert!(res.is_ok());
 
-        let res = client
-            .model_classification_labels(TIMM_MODEL.to_string())
-            .await;
+        let res = client.model_classification_labels(TIMM_MODEL).await;
         assert!(res.is_ok());
         let resp = res.unwrap();
         assert_eq!(resp.labels.len(), 1000);
-        let _ = client.unload_model(TIMM_MODEL.to_string()).await;
+        let _ = client.unload_model(TIMM_MODEL).await;
     })
     .await;
 }